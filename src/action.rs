@@ -0,0 +1,150 @@
+//! Everything that can be done from the timer, whichever way it is asked for.
+//!
+//! Keys are turned into actions by [`Action::from_key`], and the command
+//! palette lists every action with its key so it can be found by name.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::config::Theme;
+use crate::export::ExportFormat;
+use crate::ui::ScreenId;
+
+/// Something the user asks the application to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Start the timer, or pause or resume it once started.
+    StartPause,
+    Reset,
+    Skip,
+    Void,
+    Undo,
+    Name,
+    /// Add the configured small step to the running session.
+    Extend,
+    Shorten,
+    /// Add the configured large step to the running session.
+    ExtendMore,
+    ShortenMore,
+    Show(ScreenId),
+    Back,
+    NextScreen,
+    PreviousScreen,
+    SetTheme(Theme),
+    /// Write the whole history to a file in the current directory.
+    Export(ExportFormat),
+    OpenPalette,
+    /// Hand the terminal back to the shell until the job is resumed.
+    Suspend,
+    Quit,
+}
+
+impl Action {
+    /// Every action, in the order the palette lists them.
+    pub fn all() -> Vec<Self> {
+        let mut actions = vec![
+            Self::StartPause,
+            Self::Reset,
+            Self::Skip,
+            Self::Void,
+            Self::Undo,
+            Self::Name,
+            Self::Extend,
+            Self::Shorten,
+            Self::ExtendMore,
+            Self::ShortenMore,
+        ];
+        actions.extend(ScreenId::ALL.map(Self::Show));
+        actions.extend([Self::Back, Self::NextScreen, Self::PreviousScreen]);
+        actions.extend(Theme::ALL.map(Self::SetTheme));
+        actions
+            .extend([ExportFormat::Csv, ExportFormat::Json, ExportFormat::Ics].map(Self::Export));
+        actions.extend([Self::Suspend, Self::Quit]);
+        actions
+    }
+
+    /// What the action does, as listed in the palette.
+    pub fn title(self) -> String {
+        match self {
+            Self::StartPause => "Start, pause or resume the timer".to_string(),
+            Self::Reset => "Reset the timer".to_string(),
+            Self::Skip => "Skip to the next session".to_string(),
+            Self::Void => "Void the session".to_string(),
+            Self::Undo => "Undo the last reset, skip or void".to_string(),
+            Self::Name => "Name the session".to_string(),
+            Self::Extend => "Extend the session".to_string(),
+            Self::Shorten => "Shorten the session".to_string(),
+            Self::ExtendMore => "Extend the session by a large step".to_string(),
+            Self::ShortenMore => "Shorten the session by a large step".to_string(),
+            Self::Show(id) => format!("Go to {}", id.title()),
+            Self::Back => "Go back".to_string(),
+            Self::NextScreen => "Go to the next screen".to_string(),
+            Self::PreviousScreen => "Go to the previous screen".to_string(),
+            Self::SetTheme(theme) => format!("Use the {} theme", theme.as_str()),
+            Self::Export(format) => format!("Export the history as {}", format.name()),
+            Self::OpenPalette => "Open the command palette".to_string(),
+            Self::Suspend => "Suspend to the shell".to_string(),
+            Self::Quit => "Quit".to_string(),
+        }
+    }
+
+    /// The key bound to the action, as shown in the palette.
+    pub const fn key_hint(self) -> Option<&'static str> {
+        Some(match self {
+            Self::StartPause => "Space",
+            Self::Reset => "R",
+            Self::Skip => "S",
+            Self::Void => "V",
+            Self::Undo => "U",
+            Self::Name => "N",
+            Self::Extend => "+",
+            Self::Shorten => "-",
+            Self::ExtendMore => "]",
+            Self::ShortenMore => "[",
+            Self::Show(ScreenId::Timer) | Self::SetTheme(_) | Self::Export(_) => return None,
+            Self::Show(ScreenId::Fullscreen) => "F",
+            Self::Show(ScreenId::Clock) => "C",
+            Self::Show(ScreenId::Stats) => "T",
+            Self::Show(ScreenId::History) => "L",
+            Self::Show(ScreenId::Settings) => "O",
+            Self::Show(ScreenId::Help) => "H",
+            Self::Back => "Esc",
+            Self::NextScreen => "Tab",
+            Self::PreviousScreen => "Shift+Tab",
+            Self::OpenPalette => ":",
+            Self::Suspend => "Ctrl+Z",
+            Self::Quit => "Q",
+        })
+    }
+
+    /// The action a key stands for outside of text fields, if any.
+    pub fn from_key(key: KeyEvent) -> Option<Self> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return match key.code {
+                KeyCode::Char('c') => Some(Self::Quit),
+                KeyCode::Char('p') => Some(Self::OpenPalette),
+                KeyCode::Char('z') => Some(Self::Suspend),
+                _ => None,
+            };
+        }
+
+        Some(match key.code {
+            KeyCode::Char(' ') | KeyCode::Enter => Self::StartPause,
+            KeyCode::Char('r') => Self::Reset,
+            KeyCode::Char('s') => Self::Skip,
+            KeyCode::Char('v') => Self::Void,
+            KeyCode::Char('u') => Self::Undo,
+            KeyCode::Char('n') => Self::Name,
+            KeyCode::Char('+' | '=') => Self::Extend,
+            KeyCode::Char('-') => Self::Shorten,
+            KeyCode::Char(']') => Self::ExtendMore,
+            KeyCode::Char('[') => Self::ShortenMore,
+            KeyCode::Char(':') => Self::OpenPalette,
+            KeyCode::Char('q') => Self::Quit,
+            KeyCode::Esc => Self::Back,
+            KeyCode::Tab => Self::NextScreen,
+            KeyCode::BackTab => Self::PreviousScreen,
+            KeyCode::Char(c) => Self::Show(ScreenId::from_key(c)?),
+            _ => return None,
+        })
+    }
+}
@@ -1,153 +1,774 @@
-use chrono::{DateTime, Local};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use chrono::{DateTime, Local, NaiveDate};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
+use crate::action::Action;
+use crate::completion::Completions;
+use crate::config::Config;
+use crate::export::{self, ExportFilter, ExportFormat};
+use crate::goals::{GoalLog, GoalProgress};
+use crate::history::{History, SessionKind, SessionRecord};
 use crate::logger;
+use crate::palette::Palette;
+use crate::signals::SignalEvent;
+use crate::snapshot::Snapshot;
+use crate::streaks::Streaks;
 use crate::timer::{PomodoroTimer, TimerState};
+use crate::ui;
 use crate::ui::screens::{
-    clock::ClockScreen, fullscreen::FullscreenScreen, help::HelpScreen, normal::NormalScreen,
-    Screen,
+    clock::ClockScreen,
+    fullscreen::FullscreenScreen,
+    help::HelpScreen,
+    history::{HistoryChange, HistoryScreen},
+    inline::InlineScreen,
+    normal::NormalScreen,
+    settings::{Settings, SettingsScreen},
+    stats::StatsScreen,
+    Screen, ScreenId, ScreenKey, ScreenRequest,
 };
+use crate::ui::text_input::TextInput;
 
+/// How long a toast message stays on screen.
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// How long a reset, skip or void can be undone.
+const UNDO_WINDOW: Duration = Duration::from_secs(60);
+
+/// Most actions kept for undoing.
+const MAX_UNDO: usize = 10;
+
+/// A short message shown on top of the current screen.
+pub struct Toast {
+    pub message: String,
+    shown_at: Instant,
+}
+
+/// Field of the reflection modal that receives input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReflectionField {
+    Note,
+    Rating,
+}
+
+/// Answers being typed in the reflection modal after a work session.
+pub struct Reflection {
+    /// The session the answers are stored with.
+    pub session_id: u64,
+    pub note: TextInput,
+    pub rating: Option<u8>,
+    pub focus: ReflectionField,
+}
+
+/// An action on the timer that throws away the running session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerAction {
+    /// Stop the timer and start the cycle over.
+    Reset,
+    /// End the session early and move on to the next one.
+    Skip,
+    /// Start the session over without recording it.
+    Void,
+}
+
+impl TimerAction {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Reset => "reset",
+            Self::Skip => "skip",
+            Self::Void => "void",
+        }
+    }
+
+    /// Question asked before the action when it needs confirmation.
+    pub const fn question(self) -> &'static str {
+        match self {
+            Self::Reset => "Reset the timer and the cycle? [y/N]",
+            Self::Skip => "Skip to the next session? [y/N]",
+            Self::Void => "Void this session? It will not be recorded. [y/N]",
+        }
+    }
+
+    const fn done(self) -> &'static str {
+        match self {
+            Self::Reset => "Timer reset",
+            Self::Skip => "Session skipped",
+            Self::Void => "Session voided",
+        }
+    }
+}
+
+/// The timer as it was before an action, to go back to it.
+struct Undo {
+    action: TimerAction,
+    timer: PomodoroTimer,
+    sessions_completed: u32,
+    current_session_start: Option<DateTime<Local>>,
+    segment_start: Option<DateTime<Local>>,
+    segment_pauses: u32,
+    segment_adjustment: i64,
+    /// The id the action recorded a session with in the history, removed on undo.
+    recorded: Option<u64>,
+    taken_at: Instant,
+}
+
+#[allow(clippy::struct_excessive_bools)]
 pub struct App {
+    pub config: Config,
     pub timer: PomodoroTimer,
     pub should_quit: bool,
     pub sessions_completed: u32,
     pub current_session_start: Option<DateTime<Local>>,
     pub current_session_name: String,
     pub naming_mode: bool,
-    pub naming_input: String,
-    pub current_screen: Box<dyn Screen>,
+    pub naming_input: TextInput,
+    /// Previously used names offered while naming a session.
+    pub completions: Completions,
+    /// Screens opened on top of each other, the timer at the bottom.
+    screens: Vec<Box<dyn Screen>>,
+    pub inline: bool,
+    pub history: History,
+    pub goal_progress: GoalProgress,
+    pub streaks: Streaks,
+    /// The day goal progress and streaks were computed for.
+    goals_date: NaiveDate,
+    pub toast: Option<Toast>,
+    pub reflection: Option<Reflection>,
+    /// The command palette, while it is open.
+    pub palette: Option<Palette>,
+    /// Action waiting for the user to confirm it.
+    pub confirm: Option<TimerAction>,
+    /// The interface should give the terminal back to the shell.
+    pub suspend_requested: bool,
+    /// The signal that asked tomat to quit, if any.
+    pub quit_signal: Option<&'static str>,
+    goal_log: GoalLog,
+    /// Recent actions that can be undone, oldest first.
+    undo_stack: Vec<Undo>,
+    /// When the session currently on the timer (work or break) began.
+    segment_start: Option<DateTime<Local>>,
+    /// How many times the session currently on the timer was paused.
+    segment_pauses: u32,
+    /// Seconds added to (or removed from) the session currently on the timer.
+    segment_adjustment: i64,
     last_tick: Instant,
 }
 
 impl App {
-    pub fn new(work_duration: u32, break_duration: u32, long_break_duration: u32) -> Self {
-        Self {
-            timer: PomodoroTimer::new(work_duration, break_duration, long_break_duration),
+    pub fn new(config: Config) -> Self {
+        let history = History::open_default();
+        let goal_log = GoalLog::open_default();
+        let today = Local::now().date_naive();
+        let goal_progress = GoalProgress::compute(&config.goals, &history, today);
+        let streaks = Streaks::compute(&config, &history, &goal_log, today);
+        let timer = PomodoroTimer::new(
+            config.timer.work_minutes,
+            config.timer.short_break_minutes,
+            config.timer.long_break_minutes,
+        );
+        let mut app = Self {
+            config,
+            timer,
             should_quit: false,
             sessions_completed: 0,
             current_session_start: None,
             current_session_name: String::new(),
             naming_mode: false,
-            naming_input: String::new(),
-            current_screen: Box::new(NormalScreen),
+            naming_input: TextInput::default(),
+            completions: Completions::default(),
+            screens: vec![Box::new(NormalScreen)],
+            inline: false,
+            history,
+            goal_progress,
+            streaks,
+            goals_date: today,
+            toast: None,
+            reflection: None,
+            palette: None,
+            confirm: None,
+            suspend_requested: false,
+            quit_signal: None,
+            goal_log,
+            undo_stack: Vec::new(),
+            segment_start: None,
+            segment_pauses: 0,
+            segment_adjustment: 0,
             last_tick: Instant::now(),
-        }
+        };
+        app.apply_timer_config();
+        app
+    }
+
+    /// Give the timer the durations and policies of the configuration. The
+    /// session on the timer keeps its length; the next ones use the new one.
+    fn apply_timer_config(&mut self) {
+        let timer = &self.config.timer;
+        self.timer.set_durations(
+            timer.work_minutes,
+            timer.short_break_minutes,
+            timer.long_break_minutes,
+        );
+        self.timer
+            .set_long_break_interval(timer.long_break_interval);
+        self.timer
+            .set_auto_start(timer.auto_start_breaks, timer.auto_start_work);
+    }
+
+    /// Switch to the compact inline layout used with `--inline`.
+    pub fn enable_inline_mode(&mut self) {
+        self.inline = true;
+        self.screens = vec![self.home_screen()];
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) -> bool {
         if self.naming_mode {
             self.handle_naming_input(key);
-        } else {
+        } else if self.palette.is_some() {
+            self.handle_palette_input(key);
+        } else if self.reflection.is_some() {
+            self.handle_reflection_input(key);
+        } else if let Some(action) = self.confirm.take() {
             match key.code {
-                KeyCode::Char('q') => {
-                    self.should_quit = true;
-                }
+                KeyCode::Char('y' | 'Y') => self.run_action(action),
                 KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                     self.should_quit = true;
                 }
-                KeyCode::Char('f') => {
-                    self.toggle_screen();
-                }
-                KeyCode::Char('h') => {
-                    self.show_help();
-                }
-                KeyCode::Char('c') => {
-                    self.show_clock();
-                }
-                KeyCode::Char(' ') | KeyCode::Enter => {
-                    if self.timer.state() == TimerState::NotStarted {
-                        self.start_timer();
-                    } else {
-                        self.toggle_pause();
-                    }
-                }
-                KeyCode::Char('r') => {
-                    self.reset_timer();
-                }
-                KeyCode::Char('s') => {
-                    self.skip_session();
-                }
-                KeyCode::Char('n') => {
-                    // Allow naming sessions in any state except when already in naming mode
-                    if !self.naming_mode {
-                        self.enter_naming_mode();
-                    }
-                }
                 _ => {}
             }
+        } else if self.handle_screen_key(key) {
+            // Used by the screen itself, such as moving in a list
+        } else if let Some(action) = Action::from_key(key) {
+            self.perform(action);
         }
 
         self.should_quit
     }
 
+    /// Handle a click or scroll in a terminal of size `area`. Modals only
+    /// take keys, so the mouse does nothing while one is open.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, area: Rect) {
+        if self.naming_mode
+            || self.palette.is_some()
+            || self.reflection.is_some()
+            || self.confirm.is_some()
+        {
+            return;
+        }
+
+        let screen_area = ui::screen_area(self, area);
+        if mouse.row < screen_area.y {
+            if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+                if let Some(id) = ui::tab_at(area.x, mouse.column) {
+                    self.switch_to(id);
+                }
+            }
+            return;
+        }
+
+        // The compact view shown when the screen does not fit has nothing to click
+        let min_size = self.current_screen().min_size();
+        if screen_area.width < min_size.width || screen_area.height < min_size.height {
+            return;
+        }
+        let timer_data = ui::timer_data(self);
+        let action = self
+            .screens
+            .last_mut()
+            .and_then(|screen| screen.handle_mouse(mouse, &timer_data, screen_area));
+        if let Some(action) = action {
+            self.perform(action);
+        }
+    }
+
+    /// Do what a signal asks for. Scripts cannot answer a confirmation, so
+    /// skipping happens right away, and can still be undone.
+    pub fn handle_signal(&mut self, signal: SignalEvent) {
+        match signal {
+            SignalEvent::Quit(name) => {
+                info!("Received {name}, quitting");
+                self.save_snapshot();
+                self.quit_signal = Some(name);
+                self.should_quit = true;
+            }
+            SignalEvent::Suspend => self.suspend_requested = true,
+            SignalEvent::TogglePause => self.perform(Action::StartPause),
+            SignalEvent::Skip => {
+                if self.timer.state() != TimerState::NotStarted {
+                    self.run_action(TimerAction::Skip);
+                }
+            }
+        }
+    }
+
+    /// The timer and the session on it, as they are now.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            saved_at: Local::now(),
+            timer: self.timer.clone(),
+            sessions_completed: self.sessions_completed,
+            session_name: self.current_session_name.clone(),
+            current_session_start: self.current_session_start,
+            segment_start: self.segment_start,
+            segment_pauses: self.segment_pauses,
+            segment_adjustment: self.segment_adjustment,
+        }
+    }
+
+    /// Save the timer so the next start picks up from here.
+    pub fn save_snapshot(&self) {
+        if self.timer.state() == TimerState::NotStarted {
+            return;
+        }
+        match self.snapshot().save() {
+            Ok(path) => info!("Saved the timer to {}", path.display()),
+            Err(e) => error!("Failed to save the timer: {e}"),
+        }
+    }
+
+    /// Carry on from a saved timer, paused until the user is back.
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        self.timer = snapshot.timer;
+        self.timer.pause();
+        self.apply_timer_config();
+        self.sessions_completed = snapshot.sessions_completed;
+        self.current_session_name = snapshot.session_name;
+        self.current_session_start = snapshot.current_session_start;
+        self.segment_start = snapshot.segment_start;
+        self.segment_pauses = snapshot.segment_pauses;
+        self.segment_adjustment = snapshot.segment_adjustment;
+        info!(
+            "Restored the timer saved at {}",
+            snapshot.saved_at.format("%Y-%m-%d %H:%M:%S")
+        );
+        self.show_toast(format!(
+            "Restored the timer from {} · [Space] to resume",
+            snapshot.saved_at.format("%H:%M")
+        ));
+    }
+
+    /// Do what the user asked for, with a key or from the command palette.
+    pub fn perform(&mut self, action: Action) {
+        match action {
+            Action::StartPause => {
+                if self.timer.state() == TimerState::NotStarted {
+                    self.start_timer();
+                } else {
+                    self.toggle_pause();
+                }
+            }
+            Action::Reset => self.request_action(TimerAction::Reset),
+            Action::Skip => self.request_action(TimerAction::Skip),
+            Action::Void => self.request_action(TimerAction::Void),
+            Action::Undo => self.undo(),
+            Action::Name => self.enter_naming_mode(),
+            Action::Extend => self.adjust_session(i64::from(self.config.timer.adjust_minutes)),
+            Action::Shorten => self.adjust_session(-i64::from(self.config.timer.adjust_minutes)),
+            Action::ExtendMore => {
+                self.adjust_session(i64::from(self.config.timer.adjust_large_minutes));
+            }
+            Action::ShortenMore => {
+                self.adjust_session(-i64::from(self.config.timer.adjust_large_minutes));
+            }
+            Action::Show(id) => self.show(id),
+            Action::Back => self.go_back(),
+            Action::NextScreen => self.cycle_screen(true),
+            Action::PreviousScreen => self.cycle_screen(false),
+            Action::SetTheme(theme) => {
+                self.config.ui.theme = theme;
+                self.notify_config_changed();
+                self.show_toast(format!("Theme: {}", theme.as_str()));
+            }
+            Action::Export(format) => self.export_history(format),
+            Action::OpenPalette => self.palette = Some(Palette::new()),
+            Action::Suspend => self.suspend_requested = true,
+            Action::Quit => self.should_quit = true,
+        }
+    }
+
     pub fn tick(&mut self) {
         let now = Instant::now();
         let elapsed = now.duration_since(self.last_tick);
 
+        // Capture the running session before the timer moves on to the next one
+        let previous_kind = SessionKind::from_state(self.timer.state());
+        let planned = self.timer.total_duration();
+
         let session_completed = self.timer.tick(elapsed);
         if session_completed {
             self.sessions_completed += 1;
-            let session_type = match self.timer.state() {
-                TimerState::Work | TimerState::WorkPaused => "work",
-                TimerState::ShortBreak | TimerState::ShortBreakPaused => "short break",
-                TimerState::LongBreak | TimerState::LongBreakPaused => "long break",
-                TimerState::NotStarted => "unknown",
+            if let Some(kind) = previous_kind {
+                logger::log_session_complete(&self.session_event(
+                    kind,
+                    self.segment_id(),
+                    planned,
+                    planned,
+                ));
+                let recorded = self.record_session(kind, planned, planned, true);
+                if let Some(id) =
+                    recorded.filter(|_| kind == SessionKind::Work && self.config.reflection.prompt)
+                {
+                    self.reflection = Some(Reflection {
+                        session_id: id,
+                        note: TextInput::default(),
+                        rating: None,
+                        focus: ReflectionField::Note,
+                    });
+                }
+            }
+            self.segment_start = Some(Local::now());
+            self.log_session_start();
+            if self.timer.is_paused() {
+                // Waiting for the user to start it; not a pause they asked for
+                if let Some(event) = self.current_session_event() {
+                    logger::log_session_pause(&event);
+                }
+            }
+            self.announce_next_session();
+
+            if matches!(
+                self.timer.state(),
+                TimerState::Work | TimerState::WorkPaused
+            ) {
+                self.current_session_start = Some(Local::now());
+            }
+        }
+
+        // Today's goals start over at midnight, and streaks may have broken
+        if Local::now().date_naive() != self.goals_date {
+            self.update_goals();
+        }
+
+        if self
+            .toast
+            .as_ref()
+            .is_some_and(|toast| toast.shown_at.elapsed() >= TOAST_DURATION)
+        {
+            self.toast = None;
+        }
+
+        self.last_tick = now;
+    }
+
+    /// How long until the next tick changes what is shown: the countdown
+    /// moving to its next second, or the clock doing so.
+    pub fn until_next_tick(&self) -> Duration {
+        let second = Duration::from_secs(1);
+        let clock = second.saturating_sub(Duration::from_nanos(u64::from(
+            Local::now().timestamp_subsec_nanos(),
+        )));
+        let running = !self.timer.is_paused() && self.timer.state() != TimerState::NotStarted;
+        if running {
+            let countdown = self.timer.time_remaining().subsec_nanos();
+            if countdown > 0 {
+                return clock.min(Duration::from_nanos(u64::from(countdown)));
+            }
+        }
+        clock
+    }
+
+    /// Announce the session that follows a completed one, as configured.
+    fn announce_next_session(&mut self) {
+        if self.config.notifications.bell {
+            ring_bell();
+        }
+        if self.config.notifications.message {
+            let next = match self.timer.state() {
+                TimerState::Work | TimerState::WorkPaused => "Time to focus",
+                TimerState::LongBreak | TimerState::LongBreakPaused => "Time for a long break",
+                _ => "Time for a break",
             };
-            let session_name = if self.current_session_name.is_empty() {
-                None
+            let message = if self.timer.is_paused() {
+                format!("{next} · [Space] to start")
             } else {
-                Some(self.current_session_name.as_str())
+                next.to_string()
             };
-            logger::log_session_complete(session_type, session_name);
+            self.show_toast(message);
+        }
+    }
 
-            if matches!(self.timer.state(), TimerState::Work) {
-                self.current_session_start = Some(Local::now());
+    /// Record the session that just ended to the history, returning the id
+    /// it was recorded with, or nothing if it could not be.
+    fn record_session(
+        &mut self,
+        kind: SessionKind,
+        planned: Duration,
+        actual: Duration,
+        completed: bool,
+    ) -> Option<u64> {
+        let ended_at = Local::now();
+        let mut record = SessionRecord::new(
+            kind,
+            self.session_name(kind),
+            self.segment_start.unwrap_or(ended_at),
+            ended_at,
+            planned,
+            actual,
+            completed,
+        );
+        record.pauses = std::mem::take(&mut self.segment_pauses);
+        record.adjusted_secs = std::mem::take(&mut self.segment_adjustment);
+        let id = self
+            .history
+            .append(record)
+            .map_err(|e| error!("Failed to record session: {e}"))
+            .ok();
+
+        if kind == SessionKind::Work {
+            self.update_goals();
+        }
+        self.notify_history_changed();
+        id
+    }
+
+    /// Recompute goal progress and streaks, celebrating goals that were just reached.
+    fn update_goals(&mut self) {
+        let today = Local::now().date_naive();
+        self.goal_progress = GoalProgress::compute(&self.config.goals, &self.history, today);
+        for reached in self.goal_log.record_reached(&self.goal_progress) {
+            logger::log_goal_reached(&reached);
+            if self.config.goals.celebrate {
+                self.show_toast(reached.message());
+                ring_bell();
             }
         }
+        self.streaks = Streaks::compute(&self.config, &self.history, &self.goal_log, today);
+        self.goals_date = today;
+    }
 
-        self.last_tick = now;
+    /// Name recorded for a session of `kind`; breaks are never named.
+    fn session_name(&self, kind: SessionKind) -> &str {
+        if kind == SessionKind::Work {
+            &self.current_session_name
+        } else {
+            ""
+        }
     }
 
-    fn toggle_pause(&mut self) {
-        let session_type = match self.timer.state() {
-            TimerState::Work | TimerState::WorkPaused => "work",
-            TimerState::ShortBreak | TimerState::ShortBreakPaused => "short break",
-            TimerState::LongBreak | TimerState::LongBreakPaused => "long break",
-            TimerState::NotStarted => "unknown",
-        };
+    /// Identifier of the session on the timer, as used in the history.
+    fn segment_id(&self) -> u64 {
+        SessionRecord::id_for(&self.segment_start.unwrap_or_else(Local::now))
+    }
 
+    /// Describe a session for the log, with the timer in its current state.
+    fn session_event(
+        &self,
+        kind: SessionKind,
+        id: u64,
+        planned: Duration,
+        elapsed: Duration,
+    ) -> logger::SessionEvent {
+        logger::SessionEvent {
+            id,
+            kind,
+            name: self.session_name(kind).to_string(),
+            state: self.timer.state(),
+            planned_secs: planned.as_secs(),
+            elapsed_secs: elapsed.as_secs(),
+        }
+    }
+
+    /// Describe the session on the timer for the log, if there is one.
+    fn current_session_event(&self) -> Option<logger::SessionEvent> {
+        let kind = SessionKind::from_state(self.timer.state())?;
+        let planned = self.timer.total_duration();
+        let elapsed = planned.saturating_sub(self.timer.time_remaining());
+        Some(self.session_event(kind, self.segment_id(), planned, elapsed))
+    }
+
+    fn log_session_start(&self) {
+        if let Some(event) = self.current_session_event() {
+            logger::log_session_start(&event);
+        }
+    }
+
+    pub fn show_toast(&mut self, message: String) {
+        self.toast = Some(Toast {
+            message,
+            shown_at: Instant::now(),
+        });
+    }
+
+    /// Record the running session as ended early, if there is one,
+    /// returning its id.
+    fn record_interrupted_session(&mut self) -> Option<u64> {
+        let kind = SessionKind::from_state(self.timer.state())?;
+        let planned = self.timer.total_duration();
+        let actual = planned.saturating_sub(self.timer.time_remaining());
+        self.record_session(kind, planned, actual, false)
+    }
+
+    fn toggle_pause(&mut self) {
         if self.timer.is_paused() {
             self.timer.resume();
-            logger::log_session_resume(session_type);
+            if let Some(event) = self.current_session_event() {
+                logger::log_session_resume(&event);
+            }
         } else {
             self.timer.pause();
-            logger::log_session_pause(session_type);
+            self.segment_pauses += 1;
+            if let Some(event) = self.current_session_event() {
+                logger::log_session_pause(&event);
+            }
+        }
+    }
+
+    /// Lengthen the running session by `minutes`, or shorten it when negative.
+    fn adjust_session(&mut self, minutes: i64) {
+        let adjusted = self.timer.adjust(minutes * 60);
+        if adjusted == 0 {
+            return;
         }
+        self.segment_adjustment += adjusted;
+        if let Some(event) = self.current_session_event() {
+            logger::log_session_adjust(&event, adjusted);
+        }
+        let minutes_left = self.timer.time_remaining().as_secs().div_ceil(60);
+        let message = if adjusted > 0 {
+            format!("+{} min · {minutes_left} min left", adjusted / 60)
+        } else {
+            format!(
+                "−{} min · {minutes_left} min left",
+                adjusted.unsigned_abs().div_ceil(60)
+            )
+        };
+        self.show_toast(message);
+    }
+
+    /// Run `action`, or ask for confirmation first if the configuration says so.
+    fn request_action(&mut self, action: TimerAction) {
+        if self.timer.state() == TimerState::NotStarted {
+            return;
+        }
+        let confirm = match action {
+            TimerAction::Reset => self.config.confirm.reset,
+            TimerAction::Skip => self.config.confirm.skip,
+            TimerAction::Void => self.config.confirm.void,
+        };
+        if confirm {
+            self.confirm = Some(action);
+        } else {
+            self.run_action(action);
+        }
+    }
+
+    /// Run `action`, keeping the timer as it was so the action can be undone.
+    fn run_action(&mut self, action: TimerAction) {
+        let mut undo = Undo {
+            action,
+            timer: self.timer.clone(),
+            sessions_completed: self.sessions_completed,
+            current_session_start: self.current_session_start,
+            segment_start: self.segment_start,
+            segment_pauses: self.segment_pauses,
+            segment_adjustment: self.segment_adjustment,
+            recorded: None,
+            taken_at: Instant::now(),
+        };
+        undo.recorded = match action {
+            TimerAction::Reset => self.reset_timer(),
+            TimerAction::Skip => self.skip_session(),
+            TimerAction::Void => {
+                self.void_session();
+                None
+            }
+        };
+
+        self.undo_stack
+            .retain(|undo| undo.taken_at.elapsed() < UNDO_WINDOW);
+        if self.undo_stack.len() == MAX_UNDO {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(undo);
+        self.show_toast(format!("{} — press u to undo", action.done()));
     }
 
-    fn reset_timer(&mut self) {
+    /// Put the timer back as it was before the last reset, skip or void.
+    fn undo(&mut self) {
+        let Some(undo) = self
+            .undo_stack
+            .pop()
+            .filter(|undo| undo.taken_at.elapsed() < UNDO_WINDOW)
+        else {
+            self.undo_stack.clear();
+            self.show_toast("Nothing to undo".to_string());
+            return;
+        };
+
+        self.timer = undo.timer;
+        self.sessions_completed = undo.sessions_completed;
+        self.current_session_start = undo.current_session_start;
+        self.segment_start = undo.segment_start;
+        self.segment_pauses = undo.segment_pauses;
+        self.segment_adjustment = undo.segment_adjustment;
+        if let Some(id) = undo.recorded {
+            match self.history.remove(id) {
+                Ok(_) => {
+                    self.update_goals();
+                    self.notify_history_changed();
+                }
+                Err(e) => error!("Failed to remove session {id} from the history: {e}"),
+            }
+        }
+        logger::log_undo(undo.action.as_str());
+        self.show_toast(format!("Undid {}", undo.action.as_str()));
+    }
+
+    /// Stop the timer and start the cycle over, returning the id of the
+    /// session recorded as interrupted, if any.
+    fn reset_timer(&mut self) -> Option<u64> {
+        let ended = self.current_session_event();
+        let recorded = self.record_interrupted_session();
         self.timer.reset();
+        if let Some(mut event) = ended {
+            event.state = self.timer.state();
+            logger::log_session_interrupt(&event);
+        }
         self.current_session_start = None;
+        self.segment_start = None;
+        recorded
+    }
+
+    /// Start the running session over, paused, without recording it.
+    fn void_session(&mut self) {
+        let voided = self.current_session_event();
+        self.timer.restart_session();
+        if let Some(mut event) = voided {
+            event.state = self.timer.state();
+            logger::log_session_void(&event);
+        }
+        self.segment_pauses = 0;
+        self.segment_adjustment = 0;
+        self.segment_start = Some(Local::now());
+        if self.timer.state() == TimerState::WorkPaused {
+            self.current_session_start = self.segment_start;
+        }
     }
 
     fn start_timer(&mut self) {
         self.timer.start();
         self.current_session_start = Some(Local::now());
-        #[allow(clippy::cast_possible_truncation)]
-        logger::log_session_start("work", self.timer.work_duration().as_secs() as u32 / 60);
+        self.segment_start = self.current_session_start;
+        self.log_session_start();
     }
 
-    fn skip_session(&mut self) {
-        let session_type = match self.timer.state() {
-            TimerState::Work | TimerState::WorkPaused => "work",
-            TimerState::ShortBreak | TimerState::ShortBreakPaused => "short break",
-            TimerState::LongBreak | TimerState::LongBreakPaused => "long break",
-            TimerState::NotStarted => "unknown",
-        };
-        logger::log_session_skip(session_type);
+    /// Move on to the next session, returning the id of the session
+    /// recorded as skipped, if any.
+    fn skip_session(&mut self) -> Option<u64> {
+        let ended = self.current_session_event();
+        let recorded = self.record_interrupted_session();
         self.timer.skip_to_next();
+        if let Some(mut event) = ended {
+            event.state = self.timer.state();
+            logger::log_session_skip(&event);
+        }
+        if self.timer.state() != TimerState::NotStarted {
+            self.segment_start = Some(Local::now());
+            self.log_session_start();
+        }
+        recorded
     }
 
     pub const fn time_remaining(&self) -> Duration {
@@ -161,69 +782,354 @@ impl App {
     fn handle_naming_input(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Enter => {
-                self.current_session_name = self.naming_input.trim().to_string();
+                let name = self
+                    .completions
+                    .selection()
+                    .unwrap_or_else(|| self.naming_input.value());
+                self.current_session_name = name.trim().to_string();
                 self.exit_naming_mode();
             }
             KeyCode::Esc => {
                 self.exit_naming_mode();
             }
-            KeyCode::Backspace => {
-                self.naming_input.pop();
-            }
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.exit_naming_mode();
                 self.should_quit = true;
             }
-            KeyCode::Char(c) => {
-                // This handles all other characters (normal text input)
-                self.naming_input.push(c);
+            KeyCode::Tab => {
+                if let Some(name) = self.completions.candidate() {
+                    self.naming_input = TextInput::with_value(name);
+                    self.completions.update(self.naming_input.value());
+                }
+            }
+            KeyCode::Down => self.completions.select_next(),
+            KeyCode::Up => self.completions.select_previous(),
+            _ => {
+                if self.naming_input.handle_key(key) {
+                    self.completions.update(self.naming_input.value());
+                }
+            }
+        }
+    }
+
+    /// Insert pasted text into the input that has the focus, if any.
+    pub fn handle_paste(&mut self, text: &str) {
+        if self.naming_mode {
+            self.naming_input.insert_str(text);
+            self.completions.update(self.naming_input.value());
+        } else if let Some(palette) = &mut self.palette {
+            palette.input.insert_str(text);
+            palette.update();
+        } else if let Some(reflection) = &mut self.reflection {
+            if reflection.focus == ReflectionField::Note {
+                reflection.note.insert_str(text);
+            }
+        } else if let Some(screen) = self.screens.last_mut() {
+            screen.handle_paste(text);
+        }
+    }
+
+    fn handle_palette_input(&mut self, key: KeyEvent) {
+        let Some(palette) = &mut self.palette else {
+            return;
+        };
+        match key.code {
+            KeyCode::Enter => {
+                let action = palette.selection();
+                self.palette = None;
+                if let Some(action) = action {
+                    self.perform(action);
+                }
+            }
+            KeyCode::Esc => self.palette = None,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.palette = None;
+                self.should_quit = true;
+            }
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                palette.select_previous();
+            }
+            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                palette.select_next();
+            }
+            KeyCode::Up => palette.select_previous(),
+            KeyCode::Down => palette.select_next(),
+            _ => {
+                if palette.input.handle_key(key) {
+                    palette.update();
+                }
+            }
+        }
+    }
+
+    /// Write the whole history to a dated file in the current directory.
+    fn export_history(&mut self, format: ExportFormat) {
+        let result = create_export_file(format.extension()).and_then(|(path, file)| {
+            let mut out = BufWriter::new(file);
+            export::export(
+                self.history.records(),
+                &ExportFilter::default(),
+                format,
+                &mut out,
+            )?;
+            out.flush()?;
+            Ok(path)
+        });
+        match result {
+            Ok(path) => {
+                info!("Exported the history to {}", path.display());
+                self.show_toast(format!("Exported to {}", path.display()));
+            }
+            Err(e) => {
+                error!("Failed to export the history: {e}");
+                self.show_toast(format!("Failed to export: {e}"));
+            }
+        }
+    }
+
+    fn handle_reflection_input(&mut self, key: KeyEvent) {
+        let Some(reflection) = &mut self.reflection else {
+            return;
+        };
+        match key.code {
+            KeyCode::Enter => self.save_reflection(),
+            KeyCode::Esc => {
+                self.reflection = None;
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.reflection = None;
+                self.should_quit = true;
+            }
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                reflection.focus = match reflection.focus {
+                    ReflectionField::Note => ReflectionField::Rating,
+                    ReflectionField::Rating => ReflectionField::Note,
+                };
+            }
+            _ => match reflection.focus {
+                ReflectionField::Note => {
+                    reflection.note.handle_key(key);
+                }
+                ReflectionField::Rating => match key.code {
+                    KeyCode::Char(c @ '1'..='5') => {
+                        reflection.rating = c.to_digit(10).and_then(|d| u8::try_from(d).ok());
+                    }
+                    KeyCode::Right | KeyCode::Char('+') => {
+                        reflection.rating = Some(reflection.rating.map_or(1, |r| (r + 1).min(5)));
+                    }
+                    KeyCode::Left | KeyCode::Char('-') => {
+                        reflection.rating = reflection
+                            .rating
+                            .and_then(|r| r.checked_sub(1))
+                            .filter(|r| *r > 0);
+                    }
+                    KeyCode::Backspace | KeyCode::Delete | KeyCode::Char('0') => {
+                        reflection.rating = None;
+                    }
+                    _ => {}
+                },
+            },
+        }
+    }
+
+    /// Store the reflection with its session and close the modal.
+    fn save_reflection(&mut self) {
+        let Some(reflection) = self.reflection.take() else {
+            return;
+        };
+        let note = reflection.note.value().trim();
+        if note.is_empty() && reflection.rating.is_none() {
+            return;
+        }
+        match self
+            .history
+            .set_reflection(reflection.session_id, note, reflection.rating)
+        {
+            Ok(true) => {
+                self.notify_history_changed();
+                self.show_toast("📓 Reflection saved".to_string());
+            }
+            Ok(false) => warn!("Session {} not found for reflection", reflection.session_id),
+            Err(e) => {
+                error!("Failed to save reflection: {e}");
+                self.show_toast("Failed to save reflection".to_string());
             }
-            _ => {}
         }
     }
 
     fn enter_naming_mode(&mut self) {
         self.naming_mode = true;
-        self.naming_input = self.current_session_name.clone(); // Start with existing name
+        self.naming_input = TextInput::with_value(&self.current_session_name); // Start with existing name
+        self.completions = Completions::from_history(&self.history, Local::now());
+        self.completions.update(self.naming_input.value());
     }
 
     fn exit_naming_mode(&mut self) {
         self.naming_mode = false;
-        self.naming_input.clear();
+        self.naming_input = TextInput::default();
+        self.completions = Completions::default();
     }
 
-    fn toggle_screen(&mut self) {
-        if self
-            .current_screen
-            .as_any()
-            .downcast_ref::<NormalScreen>()
-            .is_some()
-        {
-            self.current_screen = Box::new(FullscreenScreen);
-        } else if self
-            .current_screen
-            .as_any()
-            .downcast_ref::<FullscreenScreen>()
-            .is_some()
-        {
-            self.current_screen = Box::new(HelpScreen);
-        } else if self
-            .current_screen
-            .as_any()
-            .downcast_ref::<HelpScreen>()
-            .is_some()
-        {
-            self.current_screen = Box::new(ClockScreen);
+    /// The screen on top of the navigation stack.
+    pub fn current_screen(&self) -> &dyn Screen {
+        self.screens
+            .last()
+            .map_or(&NormalScreen as &dyn Screen, AsRef::as_ref)
+    }
+
+    /// Open the screen `id` on top of the current one, or go back to where it
+    /// was opened from if it is already showing.
+    fn show(&mut self, id: ScreenId) {
+        if self.current_screen().id() == id {
+            self.go_back();
+        } else if let Some(position) = self.screens.iter().position(|screen| screen.id() == id) {
+            self.screens.truncate(position + 1);
+        } else {
+            let screen = self.build_screen(id);
+            self.screens.push(screen);
+        }
+    }
+
+    /// Replace the current screen with the next or previous one in the tab bar.
+    fn cycle_screen(&mut self, forward: bool) {
+        self.switch_to(self.current_screen().id().cycle(forward));
+    }
+
+    /// Replace the current screen with `id`, as when picked in the tab bar.
+    fn switch_to(&mut self, id: ScreenId) {
+        if self.current_screen().id() == id {
+            return;
+        }
+        self.screens.truncate(1);
+        if id != ScreenId::Timer {
+            let screen = self.build_screen(id);
+            self.screens.push(screen);
+        }
+    }
+
+    /// Return to the previous screen. The timer is never left this way.
+    fn go_back(&mut self) {
+        if self.screens.len() > 1 {
+            self.screens.pop();
+        }
+    }
+
+    fn build_screen(&self, id: ScreenId) -> Box<dyn Screen> {
+        match id {
+            ScreenId::Timer => self.home_screen(),
+            ScreenId::Fullscreen => Box::new(FullscreenScreen),
+            ScreenId::Clock => Box::new(ClockScreen),
+            ScreenId::Help => Box::new(HelpScreen::default()),
+            ScreenId::Stats => Box::new(StatsScreen::new(&self.history, self.streaks)),
+            ScreenId::History => Box::new(HistoryScreen::new(
+                &self.history,
+                self.timer.work_duration(),
+            )),
+            ScreenId::Settings => {
+                Box::new(SettingsScreen::new(Settings::from_config(&self.config)))
+            }
+        }
+    }
+
+    /// The timer screen at the bottom of the navigation stack.
+    fn home_screen(&self) -> Box<dyn Screen> {
+        if self.inline {
+            Box::new(InlineScreen)
         } else {
-            self.current_screen = Box::new(NormalScreen);
+            Box::new(NormalScreen)
         }
     }
 
-    fn show_help(&mut self) {
-        self.current_screen = Box::new(HelpScreen);
+    /// Let the screen on top handle a key, applying the change it asks for.
+    /// Returns whether the key was used.
+    fn handle_screen_key(&mut self, key: KeyEvent) -> bool {
+        let Some(screen) = self.screens.last_mut() else {
+            return false;
+        };
+        match screen.handle_key(key) {
+            ScreenKey::Ignored => return false,
+            ScreenKey::Handled => {}
+            ScreenKey::Request(ScreenRequest::EditHistory(change)) => {
+                self.apply_history_change(change);
+            }
+            ScreenKey::Request(ScreenRequest::ApplySettings(settings)) => {
+                settings.apply_to(&mut self.config);
+                self.apply_timer_config();
+                self.notify_config_changed();
+            }
+        }
+        true
+    }
+
+    /// Bring every open screen up to date with the configuration.
+    fn notify_config_changed(&mut self) {
+        for screen in &mut self.screens {
+            screen.config_changed(&self.config);
+        }
     }
 
-    fn show_clock(&mut self) {
-        self.current_screen = Box::new(ClockScreen);
+    /// Bring every open screen up to date with the session history.
+    fn notify_history_changed(&mut self) {
+        for screen in &mut self.screens {
+            screen.history_changed(&self.history, self.streaks);
+        }
     }
+
+    fn apply_history_change(&mut self, change: HistoryChange) {
+        let (result, done) = match change {
+            HistoryChange::Update(record) => {
+                let id = record.id;
+                (self.history.update(record), format!("Edited session {id}"))
+            }
+            HistoryChange::Remove(id) => (self.history.remove(id), format!("Deleted session {id}")),
+            HistoryChange::Insert(record) => {
+                let id = record.id;
+                (
+                    self.history.insert(record).map(|_| true),
+                    format!("Added session {id}"),
+                )
+            }
+        };
+        match result {
+            Ok(true) => {
+                info!("{done}");
+                self.show_toast("History updated".to_string());
+            }
+            Ok(false) => warn!("{done}: session not found"),
+            Err(e) => {
+                error!("Failed to update the history: {e}");
+                self.show_toast("Failed to update the history".to_string());
+            }
+        }
+
+        self.update_goals();
+        self.notify_history_changed();
+    }
+}
+
+/// Create a file named after today in the current directory to export to,
+/// numbering it rather than replacing an earlier export.
+fn create_export_file(extension: &str) -> io::Result<(PathBuf, File)> {
+    let dir = std::env::current_dir()?;
+    let stem = format!("tomat-{}", Local::now().format("%Y-%m-%d"));
+    let mut number = 1;
+    loop {
+        let name = if number == 1 {
+            format!("{stem}.{extension}")
+        } else {
+            format!("{stem}-{number}.{extension}")
+        };
+        let path = dir.join(name);
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => number += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Ring the terminal bell.
+fn ring_bell() {
+    let mut stdout = io::stdout();
+    let _ = stdout.write_all(b"\x07").and_then(|()| stdout.flush());
 }
@@ -0,0 +1,162 @@
+//! Completion of session names from the ones used before.
+//!
+//! Names are ranked by how well they match what was typed, then by how often
+//! and how recently they were used, each use counting half as much every two
+//! weeks. With nothing typed, the most used recent names are offered.
+
+use chrono::{DateTime, Local};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::history::History;
+
+/// Most suggestions offered at once.
+pub const MAX_SUGGESTIONS: usize = 5;
+
+/// Days after which a use of a name counts half as much.
+const HALF_LIFE_DAYS: f64 = 14.0;
+
+/// A previously used name that matches the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub name: String,
+    /// Byte offsets of the characters that matched the input.
+    pub matched: Vec<usize>,
+}
+
+/// Session names offered while naming a session.
+#[derive(Debug, Default)]
+pub struct Completions {
+    /// Every name used before, with its weight, most weighted first.
+    names: Vec<(String, f64)>,
+    pub suggestions: Vec<Suggestion>,
+    /// Suggestion chosen with Up/Down, if any.
+    pub selected: Option<usize>,
+}
+
+impl Completions {
+    /// Collect the names used in `history`, weighted as of `now`.
+    pub fn from_history(history: &History, now: DateTime<Local>) -> Self {
+        let mut weights: HashMap<String, f64> = HashMap::new();
+        for record in history.records() {
+            let name = record.label();
+            if name.is_empty() {
+                continue;
+            }
+            #[allow(clippy::cast_precision_loss)]
+            let age_days = (now - record.started_at).num_minutes().max(0) as f64 / 1440.0;
+            *weights.entry(name).or_default() += 0.5_f64.powf(age_days / HALF_LIFE_DAYS);
+        }
+
+        let mut names: Vec<(String, f64)> = weights.into_iter().collect();
+        names.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Self {
+            names,
+            ..Self::default()
+        }
+    }
+
+    /// Refresh the suggestions for the text typed so far.
+    pub fn update(&mut self, input: &str) {
+        let input = input.trim();
+        let mut matches: Vec<(u8, Suggestion)> = self
+            .names
+            .iter()
+            .filter(|(name, _)| name != input)
+            .filter_map(|(name, _)| {
+                let (quality, matched) = fuzzy_match(name, input)?;
+                Some((
+                    quality,
+                    Suggestion {
+                        name: name.clone(),
+                        matched,
+                    },
+                ))
+            })
+            .collect();
+        // Stable, so names of the same quality keep their weight order
+        matches.sort_by_key(|(quality, _)| Reverse(*quality));
+        self.suggestions = matches
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, suggestion)| suggestion)
+            .collect();
+        self.selected = None;
+    }
+
+    pub fn select_next(&mut self) {
+        if self.suggestions.is_empty() {
+            return;
+        }
+        self.selected = Some(
+            self.selected
+                .map_or(0, |index| (index + 1).min(self.suggestions.len() - 1)),
+        );
+    }
+
+    /// Move the selection up, back to the typed text past the first suggestion.
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.and_then(|index| index.checked_sub(1));
+    }
+
+    /// The name Tab would complete to: the selected suggestion, or the best one.
+    pub fn candidate(&self) -> Option<&str> {
+        self.suggestions
+            .get(self.selected.unwrap_or(0))
+            .map(|suggestion| suggestion.name.as_str())
+    }
+
+    /// The suggestion chosen with Up/Down, if any.
+    pub fn selection(&self) -> Option<&str> {
+        self.selected
+            .and_then(|index| self.suggestions.get(index))
+            .map(|suggestion| suggestion.name.as_str())
+    }
+}
+
+/// Match `input` against `name`, ignoring case.
+///
+/// Returns the match quality, higher being better, and the byte offsets of
+/// the matched characters. A prefix is best, then a match of word starts or a
+/// substring, then any characters in order.
+pub fn fuzzy_match(name: &str, input: &str) -> Option<(u8, Vec<usize>)> {
+    if input.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let lower_name = name.to_lowercase();
+    let lower_input = input.to_lowercase();
+    // Lowercasing can change byte lengths, so only use substring offsets when it did not
+    let substring = lower_name
+        .find(&lower_input)
+        .filter(|_| lower_name.len() == name.len())
+        .and_then(|start| Some((start, name.get(start..start + lower_input.len())?)));
+    if let Some((start, substring)) = substring {
+        let matched = substring
+            .char_indices()
+            .map(|(offset, _)| start + offset)
+            .collect();
+        return Some((if start == 0 { 3 } else { 2 }, matched));
+    }
+
+    let mut wanted = input.chars().flat_map(char::to_lowercase).peekable();
+    let mut matched = Vec::new();
+    let mut word_starts = true;
+    let mut previous: Option<char> = None;
+    for (offset, c) in name.char_indices() {
+        let Some(next) = wanted.peek() else {
+            break;
+        };
+        if c.to_lowercase().eq(std::iter::once(*next)) {
+            word_starts &= previous.map_or(true, |p| !p.is_alphanumeric());
+            matched.push(offset);
+            wanted.next();
+        }
+        previous = Some(c);
+    }
+
+    if wanted.peek().is_some() {
+        return None;
+    }
+    Some((u8::from(word_starts) + 1, matched))
+}
@@ -0,0 +1,305 @@
+//! User configuration, read from `config.toml` in the configuration directory.
+//!
+//! Every setting is optional; a missing file or section leaves the defaults.
+
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+use simplelog::LevelFilter;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::paths;
+
+/// Top-level configuration file contents.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub timer: TimerConfig,
+    pub ui: UiConfig,
+    pub notifications: NotificationsConfig,
+    pub confirm: ConfirmConfig,
+    pub goals: GoalsConfig,
+    pub streaks: StreaksConfig,
+    pub reflection: ReflectionConfig,
+    pub log: LogConfig,
+}
+
+/// Session lengths and how sessions follow each other.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimerConfig {
+    pub work_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    /// Work sessions before each long break.
+    pub long_break_interval: u32,
+    /// Start a break as soon as a work session ends, instead of waiting for a key.
+    pub auto_start_breaks: bool,
+    /// Start work as soon as a break ends, instead of waiting for a key.
+    pub auto_start_work: bool,
+    /// Minutes added or removed from the running session with `+` and `-`.
+    pub adjust_minutes: u32,
+    /// Minutes added or removed from the running session with `]` and `[`.
+    pub adjust_large_minutes: u32,
+}
+
+impl Default for TimerConfig {
+    fn default() -> Self {
+        Self {
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            long_break_interval: 4,
+            auto_start_breaks: true,
+            auto_start_work: true,
+            adjust_minutes: 1,
+            adjust_large_minutes: 5,
+        }
+    }
+}
+
+impl TimerConfig {
+    /// Check the session lengths, as a session of 0 minutes would end as soon
+    /// as it started, over and over.
+    fn validate(&self) -> Result<(), String> {
+        for (name, minutes) in [
+            ("work_minutes", self.work_minutes),
+            ("short_break_minutes", self.short_break_minutes),
+            ("long_break_minutes", self.long_break_minutes),
+        ] {
+            if minutes == 0 {
+                return Err(format!("timer.{name} must be at least 1"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Appearance settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiConfig {
+    pub theme: Theme,
+    /// Take mouse clicks and scrolling. Off leaves the terminal's own text
+    /// selection alone.
+    pub mouse: bool,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            mouse: true,
+        }
+    }
+}
+
+/// Colour scheme of the interface.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    #[default]
+    Default,
+    /// No colours, for terminals or eyes that do without them.
+    Monochrome,
+    /// Bright colours only, for readability.
+    HighContrast,
+}
+
+impl Theme {
+    pub const ALL: [Self; 3] = [Self::Default, Self::Monochrome, Self::HighContrast];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Monochrome => "monochrome",
+            Self::HighContrast => "high-contrast",
+        }
+    }
+}
+
+/// How the end of a session is announced.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationsConfig {
+    /// Ring the terminal bell.
+    pub bell: bool,
+    /// Show a message saying which session comes next.
+    pub message: bool,
+}
+
+/// Actions that ask for confirmation before they run.
+///
+/// Without confirmation they can still be undone for a while with `u`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfirmConfig {
+    pub reset: bool,
+    pub skip: bool,
+    pub void: bool,
+}
+
+/// Daily and weekly focus targets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GoalsConfig {
+    /// Completed pomodoros to aim for each day.
+    pub daily_pomodoros: Option<u32>,
+    /// Focused hours to aim for each week (Monday to Sunday).
+    pub weekly_hours: Option<f64>,
+    /// Ring the bell and show a message when a goal is reached.
+    pub celebrate: bool,
+}
+
+impl Default for GoalsConfig {
+    fn default() -> Self {
+        Self {
+            daily_pomodoros: None,
+            weekly_hours: None,
+            celebrate: true,
+        }
+    }
+}
+
+/// Streak calculation settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StreaksConfig {
+    /// Days of the week that never break a streak, e.g. `["sat", "sun"]`.
+    pub rest_days: Vec<Weekday>,
+}
+
+/// End-of-session reflection settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReflectionConfig {
+    /// Ask what got done and how focused it was after each work session.
+    pub prompt: bool,
+}
+
+/// Log file settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+    /// Log file to write to, instead of `tomat.log` in the state directory.
+    pub file: Option<PathBuf>,
+    /// Most verbose messages to write; `off` disables the log file.
+    pub level: LogLevel,
+    /// When to start a new log file.
+    pub rotation: LogRotation,
+    /// Size after which the log is rotated, with size rotation.
+    pub max_size_kb: u64,
+    /// Number of rotated log files to keep.
+    pub keep: usize,
+    /// Also write every event as a line of JSON, for `jq` and log pipelines.
+    pub events: bool,
+    /// Event log to write to, instead of `events.jsonl` in the state directory.
+    pub events_file: Option<PathBuf>,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            file: None,
+            level: LogLevel::Info,
+            rotation: LogRotation::Size,
+            max_size_kb: 1024,
+            keep: 5,
+            events: false,
+            events_file: None,
+        }
+    }
+}
+
+/// Verbosity of the log file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Off => Self::Off,
+            LogLevel::Error => Self::Error,
+            LogLevel::Warn => Self::Warn,
+            LogLevel::Info => Self::Info,
+            LogLevel::Debug => Self::Debug,
+            LogLevel::Trace => Self::Trace,
+        }
+    }
+}
+
+/// When the log file is rotated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    /// Never; the log grows without limit.
+    Never,
+    /// Once it reaches `max_size_kb`.
+    Size,
+    /// At the first message of each day.
+    Daily,
+}
+
+impl Config {
+    /// Location of the configuration file.
+    pub fn default_path() -> Option<PathBuf> {
+        paths::config_dir().map(|dir| dir.join("config.toml"))
+    }
+
+    /// Load the configuration from its default location.
+    ///
+    /// A missing file gives the defaults; an invalid one is reported on stderr
+    /// and in the log, and also falls back to the defaults.
+    pub fn load_default() -> Self {
+        let Some(path) = Self::default_path() else {
+            return Self::default();
+        };
+
+        Self::load(&path).unwrap_or_else(|e| {
+            eprintln!("Ignoring invalid configuration in {}: {e}", path.display());
+            error!("Ignoring invalid configuration in {}: {e}", path.display());
+            Self::default()
+        })
+    }
+
+    /// Change the configuration stored at `path` and write it back.
+    ///
+    /// The file is read again first, so settings overridden on the command
+    /// line are not saved. Comments in the file are not kept.
+    pub fn update_file(
+        path: &Path,
+        update: impl FnOnce(&mut Self),
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut config = Self::load(path)?;
+        update(&mut config);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temp_path = path.with_extension("toml.tmp");
+        fs::write(&temp_path, toml::to_string_pretty(&config)?)?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+
+    /// Load the configuration stored at `path`. A missing file gives the defaults.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                let config: Self = toml::from_str(&content)?;
+                config.timer.validate()?;
+                Ok(config)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
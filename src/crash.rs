@@ -0,0 +1,113 @@
+//! What happens when the interface panics: the terminal is given back, the
+//! timer is saved for the next start, and a crash report is written to
+//! `$XDG_STATE_HOME/tomat/` for a bug report.
+
+use chrono::Local;
+use std::backtrace::Backtrace;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use crate::config::Config;
+use crate::logger;
+use crate::paths;
+use crate::snapshot::Snapshot;
+use crate::timer::TimerState;
+
+/// The configuration in use, as written in the crash report.
+static CONFIG: OnceLock<String> = OnceLock::new();
+
+/// The timer as it was last drawn.
+static SNAPSHOT: Mutex<Option<Snapshot>> = Mutex::new(None);
+
+/// Install the panic hook, on top of the one restoring the terminal.
+pub fn install(config: &Config, mouse: bool) {
+    let _ = CONFIG
+        .set(toml::to_string_pretty(config).unwrap_or_else(|e| format!("Unavailable: {e}\n")));
+
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = crossterm::execute!(io::stdout(), crossterm::event::DisableBracketedPaste);
+        if mouse {
+            let _ = crossterm::execute!(io::stdout(), crossterm::event::DisableMouseCapture);
+        }
+        ratatui::restore();
+
+        let saved = save_snapshot();
+        let report = write_report(&info.to_string(), saved.as_ref());
+        error!("Crashed: {info}");
+
+        // Prints the panic message
+        previous(info);
+        match report {
+            Ok(path) => eprintln!(
+                "\ntomat crashed. A crash report was written to {}\nPlease attach it to an issue at {}/issues",
+                path.display(),
+                env!("CARGO_PKG_REPOSITORY")
+            ),
+            Err(e) => eprintln!("\ntomat crashed, and the crash report could not be written: {e}"),
+        }
+    }));
+}
+
+/// Keep the timer as it is now, to save it if the interface panics.
+pub fn record(snapshot: Snapshot) {
+    if let Ok(mut recorded) = SNAPSHOT.lock() {
+        *recorded = Some(snapshot);
+    }
+}
+
+/// Save the last recorded timer, if a session was on it. Returns where it went.
+fn save_snapshot() -> Option<PathBuf> {
+    // Never wait in the panic hook: the lock may be held by the panicking code
+    let snapshot = SNAPSHOT.try_lock().ok()?.take()?;
+    if snapshot.timer.state() == TimerState::NotStarted {
+        return None;
+    }
+    snapshot
+        .save()
+        .map_err(|e| error!("Failed to save the timer: {e}"))
+        .ok()
+}
+
+/// Write a crash report with the panic, a backtrace, the configuration and
+/// the last events. Returns where it went.
+fn write_report(panic: &str, saved: Option<&PathBuf>) -> io::Result<PathBuf> {
+    let now = Local::now();
+    let dir = paths::state_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no state directory"))?;
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("crash-{}.txt", now.format("%Y%m%d-%H%M%S")));
+
+    let mut report = format!(
+        "tomat {} ({} {}) crashed at {}\n\n{panic}\n\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        now.to_rfc3339(),
+    );
+    let _ = match saved {
+        Some(saved) => writeln!(
+            report,
+            "The timer was saved to {} and will be restored on the next start.\n",
+            saved.display()
+        ),
+        None => writeln!(report, "No session was on the timer.\n"),
+    };
+    let _ = writeln!(report, "Backtrace:\n{}\n", Backtrace::force_capture());
+    let _ = writeln!(
+        report,
+        "Configuration:\n{}",
+        CONFIG.get().map_or("Unavailable\n", String::as_str)
+    );
+    let _ = writeln!(report, "Recent events:");
+    for event in logger::recent_events() {
+        let _ = writeln!(report, "{event}");
+    }
+
+    fs::write(&path, report)?;
+    Ok(path)
+}
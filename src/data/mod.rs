@@ -1,3 +1,4 @@
+pub mod stats;
 pub mod timer;
 
 pub use timer::TimerData;
@@ -0,0 +1,38 @@
+use chrono::{Datelike, Days, NaiveDate};
+use std::collections::BTreeMap;
+
+use crate::history::{SessionKind, SessionRecord};
+
+/// Focus totals for a single day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DayTotals {
+    /// Time spent in work sessions, including ones that were cut short.
+    pub focused_secs: u64,
+    /// Number of work sessions that ran to completion.
+    pub pomodoros: u32,
+}
+
+impl DayTotals {
+    pub const fn focused_minutes(&self) -> u64 {
+        self.focused_secs / 60
+    }
+}
+
+/// Sum work sessions per local calendar day, keyed by the day they started.
+pub fn daily_totals(records: &[SessionRecord]) -> BTreeMap<NaiveDate, DayTotals> {
+    let mut totals: BTreeMap<NaiveDate, DayTotals> = BTreeMap::new();
+    for record in records.iter().filter(|r| r.kind == SessionKind::Work) {
+        let day = totals.entry(record.started_at.date_naive()).or_default();
+        day.focused_secs += record.actual_secs;
+        if record.completed {
+            day.pomodoros += 1;
+        }
+    }
+    totals
+}
+
+/// The Monday starting the week of `day`.
+pub fn week_start(day: NaiveDate) -> NaiveDate {
+    day.checked_sub_days(Days::new(u64::from(day.weekday().num_days_from_monday())))
+        .unwrap_or(day)
+}
@@ -1,15 +1,25 @@
 use std::time::Duration;
 
+use crate::goals::GoalProgress;
+use crate::streaks::Streaks;
+use crate::timer::TimerState;
+
 #[derive(Debug, Clone)]
 pub struct TimerData {
+    pub state: TimerState,
     pub remaining_time: Duration,
     pub total_time: Duration,
     pub is_running: bool,
     pub is_paused: bool,
     pub sessions_completed: u32,
     pub session_start_time: Option<String>,
+    /// Name of the session, without its project and tags.
     pub session_name: String,
+    pub project: Option<String>,
+    pub tags: Vec<String>,
     pub naming_mode: bool,
+    pub goals: GoalProgress,
+    pub streaks: Streaks,
 }
 
 impl TimerData {
@@ -20,6 +30,17 @@ impl TimerData {
         format!("{minutes:02}:{seconds:02}")
     }
 
+    /// Single-line rendering used when the terminal is too small for any panel.
+    pub fn compact_line(&self) -> String {
+        let time = if self.remaining_time.as_secs() == 0 {
+            String::from("--:--")
+        } else {
+            self.format_time()
+        };
+        let paused = if self.is_paused { " ⏸" } else { "" };
+        format!("{time} {}{paused}", self.state.label())
+    }
+
     pub fn progress_percentage(&self) -> f64 {
         // Early return 0.0 for NotStarted/paused states
         if !self.is_running || self.total_time.as_secs() == 0 {
@@ -0,0 +1,230 @@
+//! Export of the session history for `tomat export`.
+//!
+//! Sessions can be written as CSV for spreadsheets, as a JSON array, or as an
+//! iCalendar file with one event per work session to overlay focus blocks on
+//! a calendar.
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use std::io::{self, Write};
+
+use crate::history::{SessionKind, SessionRecord};
+use crate::tags::LabelFilter;
+
+/// Output format of an export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Ics,
+}
+
+impl ExportFormat {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Csv => "CSV",
+            Self::Json => "JSON",
+            Self::Ics => "iCalendar",
+        }
+    }
+
+    /// File extension of the format, without the dot.
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+            Self::Ics => "ics",
+        }
+    }
+}
+
+/// Which sessions to export.
+#[derive(Debug, Default)]
+pub struct ExportFilter {
+    /// First day to include, by start date.
+    pub from: Option<NaiveDate>,
+    /// Last day to include, by start date.
+    pub to: Option<NaiveDate>,
+    /// Only sessions whose name contains this text, ignoring case.
+    pub name: Option<String>,
+    pub kind: Option<SessionKind>,
+    /// Projects and tags the sessions must all have.
+    pub labels: Vec<LabelFilter>,
+}
+
+impl ExportFilter {
+    pub fn matches(&self, record: &SessionRecord) -> bool {
+        let day = record.started_at.date_naive();
+        self.from.map_or(true, |from| day >= from)
+            && self.to.map_or(true, |to| day <= to)
+            && self.kind.map_or(true, |kind| record.kind == kind)
+            && self.name.as_ref().map_or(true, |name| {
+                record.name.to_lowercase().contains(&name.to_lowercase())
+            })
+            && self.labels.iter().all(|label| label.matches(record))
+    }
+}
+
+/// Write the sessions matching `filter` to `out` in the given format.
+pub fn export(
+    records: &[SessionRecord],
+    filter: &ExportFilter,
+    format: ExportFormat,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let records: Vec<&SessionRecord> = records
+        .iter()
+        .filter(|record| filter.matches(record))
+        .collect();
+
+    match format {
+        ExportFormat::Csv => write_csv(&records, out),
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, &records).map_err(io::Error::other)?;
+            writeln!(out)
+        }
+        ExportFormat::Ics => write_ics(&records, out),
+    }
+}
+
+fn write_csv(records: &[&SessionRecord], out: &mut impl Write) -> io::Result<()> {
+    writeln!(
+        out,
+        "id,type,name,project,tags,started_at,ended_at,planned_minutes,actual_minutes,completed,note,rating"
+    )?;
+    for record in records {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{},{},{}",
+            record.id,
+            record.kind.as_str(),
+            csv_field(&record.name),
+            csv_field(record.project.as_deref().unwrap_or_default()),
+            csv_field(&record.tags.join(" ")),
+            record.started_at.to_rfc3339(),
+            record.ended_at.to_rfc3339(),
+            minutes(record.planned_secs),
+            minutes(record.actual_secs),
+            record.completed,
+            csv_field(&record.note),
+            record
+                .rating
+                .map(|rating| rating.to_string())
+                .unwrap_or_default()
+        )?;
+    }
+    Ok(())
+}
+
+/// Minutes with two decimals, so partial sessions keep their exact length.
+fn minutes(secs: u64) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let minutes = secs as f64 / 60.0;
+    format!("{minutes:.2}")
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn write_ics(records: &[&SessionRecord], out: &mut impl Write) -> io::Result<()> {
+    let stamp = ics_time(&Local::now());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:-//tomat//tomat {}//EN", env!("CARGO_PKG_VERSION")),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    for record in records
+        .iter()
+        .filter(|record| record.kind == SessionKind::Work)
+    {
+        let summary = if record.name.is_empty() {
+            "Focus session"
+        } else {
+            &record.name
+        };
+        let status = if record.completed {
+            "completed"
+        } else {
+            "interrupted"
+        };
+        let mut description = vec![format!(
+            "Pomodoro {status}, {} of {} minutes",
+            record.actual_secs / 60,
+            record.planned_secs / 60
+        )];
+        if let Some(rating) = record.rating {
+            description.push(format!("Focus: {rating}/5"));
+        }
+        if !record.note.is_empty() {
+            description.push(String::new());
+            description.push(record.note.clone());
+        }
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}@tomat", record.id),
+            format!("DTSTAMP:{stamp}"),
+            format!("DTSTART:{}", ics_time(&record.started_at)),
+            format!("DTEND:{}", ics_time(&record.ended_at)),
+            format!("SUMMARY:🍅 {}", ics_text(summary)),
+            format!("DESCRIPTION:{}", ics_text(&description.join("\n"))),
+        ]);
+        let categories: Vec<String> = record
+            .project
+            .iter()
+            .chain(&record.tags)
+            .map(|category| ics_text(category))
+            .collect();
+        if !categories.is_empty() {
+            lines.push(format!("CATEGORIES:{}", categories.join(",")));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    for line in lines {
+        write!(out, "{}\r\n", fold_ics_line(&line))?;
+    }
+    Ok(())
+}
+
+/// Format a time in UTC, as `20261018T143000Z`.
+fn ics_time(time: &DateTime<Local>) -> String {
+    time.with_timezone(&Utc)
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+/// Escape text values as required by RFC 5545.
+fn ics_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace(['\n', '\r'], "\\n")
+}
+
+/// Fold a content line so no line is longer than 75 bytes, without splitting
+/// a UTF-8 character.
+fn fold_ics_line(line: &str) -> String {
+    const MAX_LINE: usize = 75;
+
+    let mut folded = String::with_capacity(line.len());
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE {
+            folded.push_str("\r\n ");
+            // The leading space counts towards the continuation line
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded
+}
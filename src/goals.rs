@@ -0,0 +1,205 @@
+//! Daily and weekly focus goals.
+//!
+//! Progress is derived from the session history. Each time a goal is reached
+//! it is also appended to `goals.jsonl` in the data directory, so attainment
+//! is kept even if the targets change later.
+
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::config::GoalsConfig;
+use crate::data::stats::{daily_totals, week_start};
+use crate::history::History;
+use crate::paths;
+
+/// The period a goal applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GoalKind {
+    Daily,
+    Weekly,
+}
+
+/// Progress towards the configured goals for the current day and week.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GoalProgress {
+    /// Completed pomodoros today, and the daily target.
+    pub daily: Option<(u32, u32)>,
+    /// Focused hours this week, and the weekly target.
+    pub weekly: Option<(f64, f64)>,
+}
+
+impl GoalProgress {
+    /// Compute progress for the day `today` and the week containing it.
+    pub fn compute(config: &GoalsConfig, history: &History, today: NaiveDate) -> Self {
+        let totals = daily_totals(history.records());
+        let week_start = week_start(today);
+
+        let daily = config.daily_pomodoros.map(|target| {
+            let done = totals.get(&today).map_or(0, |day| day.pomodoros);
+            (done, target)
+        });
+        let weekly = config.weekly_hours.map(|target| {
+            let secs: u64 = totals
+                .range(week_start..=today)
+                .map(|(_, day)| day.focused_secs)
+                .sum();
+            #[allow(clippy::cast_precision_loss)]
+            let hours = secs as f64 / 3600.0;
+            (hours, target)
+        });
+
+        Self { daily, weekly }
+    }
+
+    /// Goals whose target is met.
+    pub fn reached(&self) -> Vec<GoalKind> {
+        let mut reached = Vec::new();
+        if self
+            .daily
+            .is_some_and(|(done, target)| target > 0 && done >= target)
+        {
+            reached.push(GoalKind::Daily);
+        }
+        if self
+            .weekly
+            .is_some_and(|(done, target)| target > 0.0 && done >= target)
+        {
+            reached.push(GoalKind::Weekly);
+        }
+        reached
+    }
+}
+
+/// A goal reached in a given period, as stored in the goal log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GoalAttainment {
+    pub goal: GoalKind,
+    /// The day for daily goals, the Monday of the week for weekly goals.
+    pub period: NaiveDate,
+    pub target: f64,
+    pub reached_at: DateTime<Local>,
+}
+
+/// Log of reached goals, loaded from and appended to the goal log file.
+pub struct GoalLog {
+    path: Option<PathBuf>,
+    entries: Vec<GoalAttainment>,
+}
+
+impl GoalLog {
+    /// Open the goal log at its default location, logging any failure.
+    pub fn open_default() -> Self {
+        let path = paths::data_dir().map(|dir| dir.join("goals.jsonl"));
+        let Some(path) = path else {
+            return Self {
+                path: None,
+                entries: Vec::new(),
+            };
+        };
+
+        Self::load(&path).unwrap_or_else(|e| {
+            error!("Failed to load goal log from {}: {e}", path.display());
+            Self {
+                path: Some(path),
+                entries: Vec::new(),
+            }
+        })
+    }
+
+    /// Load the goal log stored at `path`. A missing file is an empty log.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let entries = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    warn!("Skipping invalid goal log line: {e}");
+                    None
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            path: Some(path.to_path_buf()),
+            entries,
+        })
+    }
+
+    /// Periods in which `goal` was reached.
+    pub fn periods(&self, goal: GoalKind) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.goal == goal)
+            .map(|entry| entry.period)
+    }
+
+    fn contains(&self, goal: GoalKind, period: NaiveDate) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.goal == goal && entry.period == period)
+    }
+
+    fn append(&mut self, entry: GoalAttainment) -> io::Result<()> {
+        if let Some(path) = &self.path {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            let line = serde_json::to_string(&entry).map_err(io::Error::other)?;
+            writeln!(file, "{line}")?;
+        }
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Log every goal in `progress` that is met but not yet logged for its period,
+    /// returning the newly reached ones.
+    pub fn record_reached(&mut self, progress: &GoalProgress) -> Vec<GoalAttainment> {
+        let now = Local::now();
+        let today = now.date_naive();
+
+        let mut newly_reached = Vec::new();
+        for goal in progress.reached() {
+            let (period, target) = match goal {
+                GoalKind::Daily => (today, progress.daily.map_or(0.0, |(_, t)| f64::from(t))),
+                GoalKind::Weekly => (week_start(today), progress.weekly.map_or(0.0, |(_, t)| t)),
+            };
+            if self.contains(goal, period) {
+                continue;
+            }
+
+            let entry = GoalAttainment {
+                goal,
+                period,
+                target,
+                reached_at: now,
+            };
+            if let Err(e) = self.append(entry.clone()) {
+                error!("Failed to record reached goal: {e}");
+            }
+            newly_reached.push(entry);
+        }
+        newly_reached
+    }
+}
+
+impl GoalAttainment {
+    /// Message shown when the goal is reached.
+    pub fn message(&self) -> String {
+        match self.goal {
+            GoalKind::Daily => format!("🎉 Daily goal reached: {} pomodoros", self.target),
+            GoalKind::Weekly => format!("🎉 Weekly goal reached: {} focused hours", self.target),
+        }
+    }
+}
@@ -0,0 +1,481 @@
+//! Persistent record of past sessions.
+//!
+//! Sessions are stored one JSON object per line in `history.jsonl` under the
+//! data directory, so the file can be appended to cheaply and read with `jq`.
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::paths;
+use crate::tags::Label;
+use crate::timer::TimerState;
+
+/// Type of a recorded session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionKind {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl SessionKind {
+    /// The session kind a timer state belongs to, if any.
+    pub const fn from_state(state: TimerState) -> Option<Self> {
+        match state {
+            TimerState::Work | TimerState::WorkPaused => Some(Self::Work),
+            TimerState::ShortBreak | TimerState::ShortBreakPaused => Some(Self::ShortBreak),
+            TimerState::LongBreak | TimerState::LongBreakPaused => Some(Self::LongBreak),
+            TimerState::NotStarted => None,
+        }
+    }
+
+    /// The timer state of a running session of this kind.
+    pub const fn running_state(self) -> TimerState {
+        match self {
+            Self::Work => TimerState::Work,
+            Self::ShortBreak => TimerState::ShortBreak,
+            Self::LongBreak => TimerState::LongBreak,
+        }
+    }
+
+    /// Human-readable name, as used in the log.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Work => "work",
+            Self::ShortBreak => "short break",
+            Self::LongBreak => "long break",
+        }
+    }
+}
+
+impl FromStr for SessionKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['-', ' '], "_").as_str() {
+            "work" | "pomodoro" => Ok(Self::Work),
+            "break" | "short" | "short_break" => Ok(Self::ShortBreak),
+            "long" | "long_break" => Ok(Self::LongBreak),
+            _ => Err(format!(
+                "unknown session type '{s}' (expected work, break or long-break)"
+            )),
+        }
+    }
+}
+
+/// A single session as stored in the history file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    pub id: u64,
+    pub kind: SessionKind,
+    /// The name without its project and tags.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    pub started_at: DateTime<Local>,
+    pub ended_at: DateTime<Local>,
+    pub planned_secs: u64,
+    pub actual_secs: u64,
+    /// Whether the session ran to the end rather than being skipped or interrupted.
+    pub completed: bool,
+    /// How many times the session was paused.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub pauses: u32,
+    /// Seconds added to the session while it ran, negative when it was
+    /// shortened. `planned_secs` already includes them.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub adjusted_secs: i64,
+    /// What got done, as written in the reflection after the session.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+    /// Focus rating from 1 to 5, from the reflection after the session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<u8>,
+}
+
+impl SessionRecord {
+    /// A new session, taking its project and tags from `name` (see [`Label`]).
+    pub fn new(
+        kind: SessionKind,
+        name: &str,
+        started_at: DateTime<Local>,
+        ended_at: DateTime<Local>,
+        planned: Duration,
+        actual: Duration,
+        completed: bool,
+    ) -> Self {
+        let label = Label::parse(name);
+        Self {
+            id: Self::id_for(&started_at),
+            kind,
+            name: label.name,
+            project: label.project,
+            tags: label.tags,
+            started_at,
+            ended_at,
+            planned_secs: planned.as_secs(),
+            actual_secs: actual.as_secs(),
+            completed,
+            pauses: 0,
+            adjusted_secs: 0,
+            note: String::new(),
+            rating: None,
+        }
+    }
+
+    /// Identifier of the session starting at `started_at`.
+    ///
+    /// Only a starting point: sessions can start in the same millisecond, or
+    /// come with their own ids from an import, so the history gives a session
+    /// another id when its own is taken.
+    pub fn id_for(started_at: &DateTime<Local>) -> u64 {
+        u64::try_from(started_at.timestamp_millis()).unwrap_or_default()
+    }
+
+    /// The name with its project and tags, as it was typed.
+    pub fn label(&self) -> String {
+        Label {
+            name: self.name.clone(),
+            project: self.project.clone(),
+            tags: self.tags.clone(),
+        }
+        .to_string()
+    }
+
+    /// Whether both are sessions of the same kind sharing some time.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && (self.started_at == other.started_at
+                || (self.started_at < other.ended_at && other.started_at < self.ended_at))
+    }
+}
+
+/// How long to wait for another tomat process to finish writing the history.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Age after which a lock is taken to be left behind by a crashed process.
+const STALE_LOCK: Duration = Duration::from_secs(30);
+
+/// The session history, loaded from and appended to the history file.
+pub struct History {
+    path: Option<PathBuf>,
+    records: Vec<SessionRecord>,
+}
+
+impl History {
+    /// Open the history at its default location.
+    ///
+    /// Failures are logged and leave an empty history, so a broken history file
+    /// never prevents the timer from running.
+    pub fn open_default() -> Self {
+        let Some(path) = paths::data_dir().map(|dir| dir.join("history.jsonl")) else {
+            warn!("No data directory found, session history will not be saved");
+            return Self {
+                path: None,
+                records: Vec::new(),
+            };
+        };
+
+        Self::load(&path).unwrap_or_else(|e| {
+            error!(
+                "Failed to load session history from {}: {e}",
+                path.display()
+            );
+            Self {
+                path: Some(path),
+                records: Vec::new(),
+            }
+        })
+    }
+
+    /// Load the history stored at `path`. A missing file is an empty history.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            path: Some(path.to_path_buf()),
+            records: read_records(path)?.0,
+        })
+    }
+
+    /// Add a session to the history and append it to the history file.
+    ///
+    /// Returns the id it was recorded with, which differs from its own when
+    /// another session already has that one.
+    pub fn append(&mut self, record: SessionRecord) -> io::Result<u64> {
+        let ids = self.append_all(vec![record])?;
+        Ok(ids[0])
+    }
+
+    /// Add sessions to the history and append them to the history file,
+    /// returning the ids they were recorded with (see [`History::append`]).
+    ///
+    /// Sessions other tomat processes recorded since the history was loaded
+    /// are read back first, so the history in memory stays complete.
+    pub fn append_all(&mut self, mut records: Vec<SessionRecord>) -> io::Result<Vec<u64>> {
+        let Some(path) = self.path.clone() else {
+            assign_unused_ids(&self.records, &mut records);
+            let ids = records.iter().map(|record| record.id).collect();
+            self.records.extend(records);
+            return Ok(ids);
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let _lock = HistoryLock::acquire(&path)?;
+        self.records = read_records(&path)?.0;
+        assign_unused_ids(&self.records, &mut records);
+        let mut content = String::new();
+        for record in &records {
+            content.push_str(&serde_json::to_string(record).map_err(io::Error::other)?);
+            content.push('\n');
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        file.write_all(content.as_bytes())?;
+
+        let ids = records.iter().map(|record| record.id).collect();
+        self.records.extend(records);
+        Ok(ids)
+    }
+
+    /// Store the reflection on a recorded session and rewrite the history file.
+    ///
+    /// Returns whether the session was found.
+    pub fn set_reflection(&mut self, id: u64, note: &str, rating: Option<u8>) -> io::Result<bool> {
+        self.modify(|records| {
+            let Some(record) = records.iter_mut().find(|record| record.id == id) else {
+                return false;
+            };
+            record.note = note.to_string();
+            record.rating = rating;
+            true
+        })
+    }
+
+    /// Replace the recorded session with the same id and rewrite the history file.
+    ///
+    /// Returns whether the session was found.
+    pub fn update(&mut self, record: SessionRecord) -> io::Result<bool> {
+        self.modify(|records| {
+            let Some(existing) = records.iter_mut().find(|r| r.id == record.id) else {
+                return false;
+            };
+            *existing = record;
+            true
+        })
+    }
+
+    /// Remove the recorded session with `id` and rewrite the history file.
+    ///
+    /// Returns whether the session was found.
+    pub fn remove(&mut self, id: u64) -> io::Result<bool> {
+        self.modify(|records| {
+            let Some(index) = records.iter().position(|record| record.id == id) else {
+                return false;
+            };
+            records.remove(index);
+            true
+        })
+    }
+
+    /// Add a session that happened in the past, keeping the sessions in the
+    /// order they started, and rewrite the history file.
+    ///
+    /// Returns the id it was recorded with, as [`History::append`] does.
+    pub fn insert(&mut self, mut record: SessionRecord) -> io::Result<u64> {
+        let mut id = record.id;
+        self.modify(|records| {
+            assign_unused_ids(records, std::slice::from_mut(&mut record));
+            id = record.id;
+            let index =
+                records.partition_point(|existing| existing.started_at <= record.started_at);
+            records.insert(index, record);
+            true
+        })?;
+        Ok(id)
+    }
+
+    /// Apply `change` to the sessions in the history file as it is now and
+    /// rewrite it if `change` returns true, which is also returned.
+    ///
+    /// The file is read back under a lock rather than written from memory, so
+    /// sessions other tomat processes recorded since the history was loaded
+    /// are kept.
+    fn modify(&mut self, change: impl FnOnce(&mut Vec<SessionRecord>) -> bool) -> io::Result<bool> {
+        let Some(path) = self.path.clone() else {
+            return Ok(change(&mut self.records));
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let _lock = HistoryLock::acquire(&path)?;
+        let (mut records, invalid_lines) = read_records(&path)?;
+        let changed = change(&mut records);
+        if changed {
+            write_records(&path, &records, &invalid_lines)?;
+        }
+        self.records = records;
+        Ok(changed)
+    }
+
+    /// Whether a session of the same kind overlapping `record` is already recorded.
+    pub fn contains_overlapping(&self, record: &SessionRecord) -> bool {
+        self.records
+            .iter()
+            .any(|existing| existing.overlaps(record))
+    }
+
+    pub fn records(&self) -> &[SessionRecord] {
+        &self.records
+    }
+}
+
+/// A lock file held while the history file is read and written, so that two
+/// tomat processes never lose what the other one wrote.
+struct HistoryLock {
+    path: PathBuf,
+}
+
+impl HistoryLock {
+    /// Take the lock on the history file at `history`, waiting for another
+    /// process holding it.
+    fn acquire(history: &Path) -> io::Result<Self> {
+        let path = history.with_extension("jsonl.lock");
+        let waiting_since = Instant::now();
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Ok(Self { path }),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e),
+            }
+
+            let age = fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok());
+            if age.is_some_and(|age| age >= STALE_LOCK) {
+                warn!("Removing the stale lock {}", path.display());
+                let _ = fs::remove_file(&path);
+            } else if waiting_since.elapsed() >= LOCK_TIMEOUT {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("{} is held by another process", path.display()),
+                ));
+            } else {
+                thread::sleep(Duration::from_millis(10));
+            }
+        }
+    }
+}
+
+impl Drop for HistoryLock {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            error!("Failed to remove {}: {e}", self.path.display());
+        }
+    }
+}
+
+/// Read the sessions stored at `path`. A missing file has none.
+/// Lines that are not a valid session are returned as they are, to be kept
+/// when the file is rewritten.
+fn read_records(path: &Path) -> io::Result<(Vec<SessionRecord>, Vec<String>)> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+
+    let mut records = Vec::new();
+    let mut invalid_lines = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(line) {
+            Ok(record) => records.push(record),
+            Err(e) => {
+                warn!("Skipping invalid history line {}: {e}", index + 1);
+                invalid_lines.push(line.to_string());
+            }
+        }
+    }
+    make_ids_unique(&mut records);
+    Ok((records, invalid_lines))
+}
+
+/// Rewrite the whole history file at `path` with `records`, followed by the
+/// `invalid_lines` it had, unchanged, so that they can still be fixed by hand.
+///
+/// The new file is written next to the old one and renamed over it, so an
+/// interruption never leaves a truncated history.
+fn write_records(
+    path: &Path,
+    records: &[SessionRecord],
+    invalid_lines: &[String],
+) -> io::Result<()> {
+    let mut content = String::new();
+    for record in records {
+        content.push_str(&serde_json::to_string(record).map_err(io::Error::other)?);
+        content.push('\n');
+    }
+    for line in invalid_lines {
+        content.push_str(line);
+        content.push('\n');
+    }
+    let temp_path = path.with_extension("jsonl.tmp");
+    fs::write(&temp_path, content)?;
+    fs::rename(&temp_path, path)
+}
+
+/// Give each of `records` an id no session in `existing` or before it in
+/// `records` has.
+fn assign_unused_ids(existing: &[SessionRecord], records: &mut [SessionRecord]) {
+    let mut taken: HashSet<u64> = existing.iter().map(|record| record.id).collect();
+    for record in records {
+        record.id = next_unused_id(&taken, record.id);
+        taken.insert(record.id);
+    }
+}
+
+/// Give every session sharing its id with an earlier one an id of its own, so
+/// that an edit or a deletion only ever touches one session.
+fn make_ids_unique(records: &mut [SessionRecord]) {
+    let mut taken: HashSet<u64> = records.iter().map(|record| record.id).collect();
+    let mut seen = HashSet::new();
+    for record in records {
+        if !seen.insert(record.id) {
+            let id = next_unused_id(&taken, record.id);
+            warn!(
+                "Session {} is recorded twice, using id {id} for the second one",
+                record.id
+            );
+            taken.insert(id);
+            seen.insert(id);
+            record.id = id;
+        }
+    }
+}
+
+/// The first id from `id` up that is not in `taken`.
+fn next_unused_id(taken: &HashSet<u64>, mut id: u64) -> u64 {
+    while taken.contains(&id) {
+        id = id.wrapping_add(1);
+    }
+    id
+}
+
+fn is_zero<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
@@ -0,0 +1,73 @@
+//! A small CSV reader following RFC 4180.
+
+/// A row of fields, with the line it starts on.
+pub struct Row {
+    pub line: usize,
+    pub fields: Vec<String>,
+}
+
+/// Split CSV content into rows of fields.
+///
+/// Fields may be quoted, with `""` standing for a quote, and quoted fields may
+/// span several lines. Blank lines are skipped.
+pub fn parse(content: &str) -> Vec<Row> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut rows = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut row_line = 1;
+
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                push_row(&mut rows, row_line, std::mem::take(&mut fields));
+                line += 1;
+                row_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        push_row(&mut rows, row_line, fields);
+    }
+    rows
+}
+
+fn push_row(rows: &mut Vec<Row>, line: usize, fields: Vec<String>) {
+    let blank = fields.len() == 1 && fields[0].trim().is_empty();
+    if !blank {
+        rows.push(Row { line, fields });
+    }
+}
+
+/// Index of each wanted column in the header row, matched ignoring case.
+pub fn column(header: &[String], name: &str) -> Option<usize> {
+    header
+        .iter()
+        .position(|column| column.trim().eq_ignore_ascii_case(name.trim()))
+}
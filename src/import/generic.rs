@@ -0,0 +1,230 @@
+//! Generic CSV files, with a configurable mapping from columns to fields.
+
+use std::time::Duration;
+
+use super::{add_labels, csv, parse_duration, parse_time, Importer, Parsed};
+use crate::history::{SessionKind, SessionRecord};
+
+/// Session fields that can be read from a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Start,
+    End,
+    Duration,
+    Planned,
+    Name,
+    Project,
+    Tags,
+    Kind,
+    Completed,
+    Note,
+    Rating,
+}
+
+impl Field {
+    const ALL: [Self; 11] = [
+        Self::Start,
+        Self::End,
+        Self::Duration,
+        Self::Planned,
+        Self::Name,
+        Self::Project,
+        Self::Tags,
+        Self::Kind,
+        Self::Completed,
+        Self::Note,
+        Self::Rating,
+    ];
+
+    /// Name used in `--column FIELD=HEADER`.
+    const fn key(self) -> &'static str {
+        match self {
+            Self::Start => "start",
+            Self::End => "end",
+            Self::Duration => "duration",
+            Self::Planned => "planned",
+            Self::Name => "name",
+            Self::Project => "project",
+            Self::Tags => "tags",
+            Self::Kind => "type",
+            Self::Completed => "completed",
+            Self::Note => "note",
+            Self::Rating => "rating",
+        }
+    }
+
+    /// Column read when no mapping is given, as written by `tomat export`.
+    const fn default_header(self) -> &'static str {
+        match self {
+            Self::Start => "started_at",
+            Self::End => "ended_at",
+            Self::Duration => "actual_minutes",
+            Self::Planned => "planned_minutes",
+            Self::Name => "name",
+            Self::Project => "project",
+            Self::Tags => "tags",
+            Self::Kind => "type",
+            Self::Completed => "completed",
+            Self::Note => "note",
+            Self::Rating => "rating",
+        }
+    }
+}
+
+/// Imports any CSV file with a header row.
+///
+/// Each session needs a start time and either an end time or a duration, in
+/// minutes or as `H:MM:SS`. Sessions without a type are work sessions, and
+/// sessions without a completion column count as completed. Tags are separated
+/// by spaces or commas, and add to any written in the name.
+pub struct GenericCsvImporter {
+    /// Header of the column for each field, and whether it was given explicitly.
+    headers: Vec<(Field, String, bool)>,
+}
+
+impl GenericCsvImporter {
+    /// Build the importer from `FIELD=HEADER` mappings.
+    pub fn new(mappings: &[String]) -> Result<Self, String> {
+        let mut headers: Vec<(Field, String, bool)> = Field::ALL
+            .iter()
+            .map(|field| (*field, field.default_header().to_string(), false))
+            .collect();
+
+        for mapping in mappings {
+            let (key, header) = mapping.split_once('=').ok_or_else(|| {
+                format!("invalid column mapping '{mapping}' (expected FIELD=HEADER)")
+            })?;
+            let entry = headers
+                .iter_mut()
+                .find(|(field, _, _)| field.key().eq_ignore_ascii_case(key.trim()))
+                .ok_or_else(|| {
+                    let keys: Vec<&str> = Field::ALL.iter().map(|field| field.key()).collect();
+                    format!(
+                        "unknown field '{}' in column mapping (expected one of {})",
+                        key.trim(),
+                        keys.join(", ")
+                    )
+                })?;
+            entry.1 = header.trim().to_string();
+            entry.2 = true;
+        }
+
+        Ok(Self { headers })
+    }
+}
+
+/// Position of each field's column in a file.
+struct Columns(Vec<(Field, usize)>);
+
+impl Columns {
+    fn get<'a>(&self, field: Field, row: &'a [String]) -> Option<&'a str> {
+        let index = self.0.iter().find(|(f, _)| *f == field)?.1;
+        row.get(index)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn has(&self, field: Field) -> bool {
+        self.0.iter().any(|(f, _)| *f == field)
+    }
+}
+
+impl Importer for GenericCsvImporter {
+    fn parse(&self, content: &str) -> Result<Parsed, String> {
+        let mut rows = csv::parse(content).into_iter();
+        let header = rows.next().ok_or("the file is empty")?.fields;
+
+        let mut columns = Vec::new();
+        for (field, name, explicit) in &self.headers {
+            match csv::column(&header, name) {
+                Some(index) => columns.push((*field, index)),
+                None if *explicit => return Err(format!("no column named '{name}'")),
+                None => {}
+            }
+        }
+        let columns = Columns(columns);
+        if !columns.has(Field::Start) {
+            return Err("no start time column (map one with --column start=HEADER)".to_string());
+        }
+        if !columns.has(Field::End) && !columns.has(Field::Duration) {
+            return Err(
+                "no end time or duration column (map one with --column end=HEADER or --column duration=HEADER)"
+                    .to_string(),
+            );
+        }
+
+        let mut parsed = Parsed::default();
+        for row in rows {
+            match record(&columns, &row.fields) {
+                Ok(record) => parsed.records.push(record),
+                Err(e) => parsed.skipped.push((row.line, e)),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// Build the session described by a row.
+fn record(columns: &Columns, row: &[String]) -> Result<SessionRecord, String> {
+    let value = |field: Field| columns.get(field, row);
+
+    let start = value(Field::Start).ok_or("missing start time")?;
+    let started_at = parse_time(start).ok_or_else(|| format!("invalid start time '{start}'"))?;
+    let duration = value(Field::Duration)
+        .map(|text| parse_duration(text).ok_or_else(|| format!("invalid duration '{text}'")))
+        .transpose()?;
+    let ended_at = if let Some(end) = value(Field::End) {
+        parse_time(end).ok_or_else(|| format!("invalid end time '{end}'"))?
+    } else {
+        let duration = duration.ok_or("missing end time and duration")?;
+        started_at
+            + chrono::Duration::from_std(duration)
+                .map_err(|_| format!("duration too long: {duration:?}"))?
+    };
+    if ended_at < started_at {
+        return Err("ends before it starts".to_string());
+    }
+
+    let actual =
+        duration.unwrap_or_else(|| (ended_at - started_at).to_std().unwrap_or(Duration::ZERO));
+    let planned = value(Field::Planned)
+        .map(|text| parse_duration(text).ok_or_else(|| format!("invalid planned length '{text}'")))
+        .transpose()?
+        .unwrap_or(actual);
+    let kind = value(Field::Kind).map_or(Ok(SessionKind::Work), str::parse)?;
+    let completed = value(Field::Completed).map_or(Ok(true), parse_bool)?;
+    let rating = value(Field::Rating)
+        .map(|text| {
+            text.parse()
+                .ok()
+                .filter(|rating| (1..=5).contains(rating))
+                .ok_or_else(|| format!("invalid rating '{text}' (expected 1 to 5)"))
+        })
+        .transpose()?;
+
+    let mut record = SessionRecord::new(
+        kind,
+        value(Field::Name).unwrap_or_default(),
+        started_at,
+        ended_at,
+        planned,
+        actual,
+        completed,
+    );
+    add_labels(
+        &mut record,
+        value(Field::Project),
+        value(Field::Tags).unwrap_or_default().split([' ', ',']),
+    );
+    record.note = value(Field::Note).unwrap_or_default().to_string();
+    record.rating = rating;
+    Ok(record)
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" => Ok(true),
+        "false" | "no" | "n" | "0" => Ok(false),
+        _ => Err(format!("invalid completion value '{value}'")),
+    }
+}
@@ -0,0 +1,33 @@
+//! Sessions exported by tomat itself.
+
+use super::{Importer, Parsed};
+use crate::history::SessionRecord;
+
+/// Imports the JSON array written by `tomat export --format json`, or the
+/// JSON Lines of a history file, such as one copied from another machine.
+pub struct JsonImporter;
+
+impl Importer for JsonImporter {
+    fn parse(&self, content: &str) -> Result<Parsed, String> {
+        if content.trim_start().starts_with('[') {
+            let records: Vec<SessionRecord> =
+                serde_json::from_str(content).map_err(|e| format!("invalid JSON: {e}"))?;
+            return Ok(Parsed {
+                records,
+                skipped: Vec::new(),
+            });
+        }
+
+        let mut parsed = Parsed::default();
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(record) => parsed.records.push(record),
+                Err(e) => parsed.skipped.push((index + 1, e.to_string())),
+            }
+        }
+        Ok(parsed)
+    }
+}
@@ -0,0 +1,155 @@
+//! Import of sessions exported by other tools, for `tomat import`.
+//!
+//! Each supported format has an [`Importer`] turning the exported file into
+//! session records. Rows that cannot be read are reported rather than failing
+//! the whole import, and sessions overlapping the existing history are skipped.
+
+mod csv;
+mod generic;
+mod json;
+mod toggl;
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use std::io;
+use std::time::Duration;
+
+use crate::history::{History, SessionRecord};
+use crate::tags;
+
+pub use generic::GenericCsvImporter;
+pub use json::JsonImporter;
+pub use toggl::TogglImporter;
+
+/// Formats understood by `tomat import`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImportFormat {
+    /// Any CSV file, with columns mapped by `--column`
+    Csv,
+    /// Detailed CSV report exported from Toggl Track
+    Toggl,
+    /// JSON written by `tomat export --format json`, or a tomat history file
+    Json,
+}
+
+/// Sessions read from an exported file.
+#[derive(Debug, Default)]
+pub struct Parsed {
+    pub records: Vec<SessionRecord>,
+    /// Line numbers of the rows that were skipped, with the reason.
+    pub skipped: Vec<(usize, String)>,
+}
+
+/// Reads sessions from the export of another tool.
+pub trait Importer {
+    /// Parse the whole exported file. Fails only if nothing can be read at all.
+    fn parse(&self, content: &str) -> Result<Parsed, String>;
+}
+
+impl ImportFormat {
+    /// Build the importer for this format, with the column mapping given on the
+    /// command line for generic CSV files.
+    pub fn importer(self, columns: &[String]) -> Result<Box<dyn Importer>, String> {
+        match self {
+            Self::Csv => Ok(Box::new(GenericCsvImporter::new(columns)?)),
+            Self::Toggl | Self::Json if !columns.is_empty() => {
+                Err("--column only applies to --from csv".to_string())
+            }
+            Self::Toggl => Ok(Box::new(TogglImporter)),
+            Self::Json => Ok(Box::new(JsonImporter)),
+        }
+    }
+}
+
+/// Result of adding imported sessions to the history.
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub imported: usize,
+    /// Sessions skipped because they overlap one already recorded.
+    pub duplicates: usize,
+}
+
+/// Append the records that do not overlap the history or each other.
+///
+/// With `dry_run`, only count what would be imported.
+pub fn add_to_history(
+    history: &mut History,
+    records: Vec<SessionRecord>,
+    dry_run: bool,
+) -> io::Result<Summary> {
+    let mut summary = Summary::default();
+    let mut accepted: Vec<SessionRecord> = Vec::new();
+    for record in records {
+        if history.contains_overlapping(&record)
+            || accepted.iter().any(|other| other.overlaps(&record))
+        {
+            summary.duplicates += 1;
+            continue;
+        }
+        accepted.push(record);
+        summary.imported += 1;
+    }
+    if !dry_run {
+        history.append_all(accepted)?;
+    }
+    Ok(summary)
+}
+
+/// Set the project and add the tags read from their own columns.
+fn add_labels<'a>(
+    record: &mut SessionRecord,
+    project: Option<&str>,
+    tags: impl IntoIterator<Item = &'a str>,
+) {
+    if let Some(project) = project.map(tags::normalize).filter(|p| !p.is_empty()) {
+        record.project = Some(project);
+    }
+    for tag in tags.into_iter().map(tags::normalize) {
+        if !tag.is_empty() && !record.tags.contains(&tag) {
+            record.tags.push(tag);
+        }
+    }
+}
+
+/// Parse a point in time, either RFC 3339 or a local date and time such as
+/// `2026-10-01 09:30` or `2026-10-01T09:30:00`.
+fn parse_time(value: &str) -> Option<DateTime<Local>> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&Local));
+    }
+    [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .and_then(|time| Local.from_local_datetime(&time).earliest())
+}
+
+/// Parse a local date and a time of day given in separate columns.
+fn parse_date_time(date: &str, time: &str) -> Option<DateTime<Local>> {
+    let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()?;
+    let time = NaiveTime::parse_from_str(time.trim(), "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(time.trim(), "%H:%M"))
+        .ok()?;
+    Local.from_local_datetime(&date.and_time(time)).earliest()
+}
+
+/// Parse a duration written as `H:MM:SS`, or as a number of minutes.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if value.contains(':') {
+        let mut secs: u64 = 0;
+        for part in value.split(':') {
+            secs = secs
+                .checked_mul(60)?
+                .checked_add(part.parse::<u64>().ok()?)?;
+        }
+        return Some(Duration::from_secs(secs));
+    }
+    let minutes: f64 = value.parse().ok()?;
+    // Rejects negative, infinite and overflowing durations
+    Duration::try_from_secs_f64(minutes * 60.0).ok()
+}
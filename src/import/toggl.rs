@@ -0,0 +1,65 @@
+//! Detailed CSV reports exported from Toggl Track.
+
+use super::{add_labels, csv, parse_date_time, parse_duration, Importer, Parsed};
+use crate::history::{SessionKind, SessionRecord};
+
+/// Imports Toggl time entries as completed work sessions named after their
+/// description, keeping their project and tags.
+pub struct TogglImporter;
+
+impl Importer for TogglImporter {
+    fn parse(&self, content: &str) -> Result<Parsed, String> {
+        let mut rows = csv::parse(content).into_iter();
+        let header = rows.next().ok_or("the file is empty")?.fields;
+        let column = |name: &str| {
+            csv::column(&header, name)
+                .ok_or_else(|| format!("no '{name}' column, is this a Toggl detailed report?"))
+        };
+
+        let start_date = column("Start date")?;
+        let start_time = column("Start time")?;
+        let end_date = column("End date")?;
+        let end_time = column("End time")?;
+        let duration = column("Duration")?;
+        let description = csv::column(&header, "Description");
+        let project = csv::column(&header, "Project");
+        let tags = csv::column(&header, "Tags");
+
+        let mut parsed = Parsed::default();
+        for row in rows {
+            let field = |index: usize| row.fields.get(index).map_or("", String::as_str);
+            let started_at = parse_date_time(field(start_date), field(start_time));
+            let ended_at = parse_date_time(field(end_date), field(end_time));
+            let (Some(started_at), Some(ended_at)) = (started_at, ended_at) else {
+                parsed
+                    .skipped
+                    .push((row.line, "invalid start or end time".to_string()));
+                continue;
+            };
+            let Some(length) = parse_duration(field(duration)) else {
+                parsed
+                    .skipped
+                    .push((row.line, format!("invalid duration '{}'", field(duration))));
+                continue;
+            };
+
+            let name = description.map_or("", field);
+            let mut record = SessionRecord::new(
+                SessionKind::Work,
+                name.trim(),
+                started_at,
+                ended_at,
+                length,
+                length,
+                true,
+            );
+            add_labels(
+                &mut record,
+                project.map(field),
+                tags.map_or("", field).split(','),
+            );
+            parsed.records.push(record);
+        }
+        Ok(parsed)
+    }
+}
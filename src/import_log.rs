@@ -0,0 +1,314 @@
+//! Reconstruction of sessions from `tomat.log` for `tomat import-log`.
+//!
+//! Before the history file existed, sessions were only written to the log as
+//! free-form lines such as `Started work session (25 minutes)`. Replaying those
+//! lines gives a best-effort history: start times and completions are exact,
+//! while sessions ended by a reset are assumed to have run until the next start
+//! or their planned length, whichever came first.
+
+use chrono::{DateTime, Local, NaiveDateTime};
+use std::time::Duration;
+
+use crate::history::{SessionKind, SessionRecord};
+
+/// Timestamp format of log lines, as configured in `logger::init_logger`.
+const TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3fZ";
+
+/// Sessions reconstructed from a log file.
+#[derive(Debug, Default)]
+pub struct LogImport {
+    pub records: Vec<SessionRecord>,
+    /// Line numbers and contents of lines that could not be understood.
+    pub unparsable: Vec<(usize, String)>,
+    /// Whether the log ends in the middle of a session, which is left out.
+    pub unfinished: bool,
+}
+
+/// A session event found in the log.
+enum Event {
+    Started(SessionKind, u32),
+    /// Minutes added to the session (negative when removed), and its new length.
+    Adjusted(i64, u32),
+    Completed(Option<String>),
+    Paused,
+    Resumed,
+    Skipped,
+    Interrupted,
+    /// The session was started over, paused, without being recorded.
+    Voided,
+    /// The timer was saved to be restored on the next start.
+    Saved,
+    AppStarted,
+    AppQuit,
+    /// A line that is understood but does not affect sessions.
+    Other,
+}
+
+/// A session that has started and not been recorded yet.
+struct Pending {
+    kind: SessionKind,
+    planned: Duration,
+    started_at: DateTime<Local>,
+    paused_at: Option<DateTime<Local>>,
+    paused: Duration,
+    pauses: u32,
+    /// Seconds added to the planned length while the session ran.
+    adjusted: i64,
+    /// Whether the timer was saved when quitting, to carry on after the restart.
+    saved: bool,
+}
+
+impl Pending {
+    /// Time spent running between the start and `ended_at`, excluding pauses.
+    fn running_time(&self, ended_at: DateTime<Local>) -> Duration {
+        let paused = self.paused
+            + self.paused_at.map_or(Duration::ZERO, |paused_at| {
+                duration_between(paused_at, ended_at)
+            });
+        duration_between(self.started_at, ended_at).saturating_sub(paused)
+    }
+
+    fn into_record(
+        self,
+        ended_at: DateTime<Local>,
+        completed: bool,
+        name: Option<&str>,
+    ) -> SessionRecord {
+        let actual = if completed {
+            self.planned
+        } else {
+            self.running_time(ended_at).min(self.planned)
+        };
+        let name = if self.kind == SessionKind::Work {
+            name.unwrap_or_default()
+        } else {
+            ""
+        };
+        let mut record = SessionRecord::new(
+            self.kind,
+            name,
+            self.started_at,
+            ended_at,
+            self.planned,
+            actual,
+            completed,
+        );
+        record.pauses = self.pauses;
+        record.adjusted_secs = self.adjusted;
+        record
+    }
+}
+
+fn duration_between(start: DateTime<Local>, end: DateTime<Local>) -> Duration {
+    (end - start).to_std().unwrap_or_default()
+}
+
+/// Reconstruct the sessions described by the contents of a log file.
+pub fn parse_log(content: &str) -> LogImport {
+    let mut import = LogImport::default();
+    let mut current: Option<Pending> = None;
+    // A session replaced by the next one, waiting for its completion line
+    let mut replaced: Option<(Pending, DateTime<Local>)> = None;
+    let mut last_seen: Option<DateTime<Local>> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some((time, event)) = parse_line(line) else {
+            import.unparsable.push((index + 1, line.to_string()));
+            continue;
+        };
+
+        // The timer logs the start of the next session before the completion of
+        // the previous one, so a replaced session is only known to have been
+        // reset once any other line follows.
+        if let Some((pending, next_start)) = replaced.take() {
+            if let Event::Completed(name) = &event {
+                import
+                    .records
+                    .push(pending.into_record(time, true, name.as_deref()));
+                last_seen = Some(time);
+                continue;
+            }
+            let ended_at = next_start.min(pending.started_at + pending.planned);
+            import
+                .records
+                .push(pending.into_record(ended_at, false, None));
+        }
+
+        match event {
+            Event::Started(kind, minutes) => {
+                let next = Pending {
+                    kind,
+                    planned: Duration::from_secs(u64::from(minutes) * 60),
+                    started_at: time,
+                    paused_at: None,
+                    paused: Duration::ZERO,
+                    pauses: 0,
+                    adjusted: 0,
+                    saved: false,
+                };
+                if let Some(pending) = current.replace(next) {
+                    replaced = Some((pending, time));
+                }
+            }
+            Event::Completed(name) => {
+                if let Some(pending) = current.take() {
+                    import
+                        .records
+                        .push(pending.into_record(time, true, name.as_deref()));
+                }
+            }
+            Event::Adjusted(minutes, planned) => {
+                if let Some(pending) = &mut current {
+                    pending.planned = Duration::from_secs(u64::from(planned) * 60);
+                    pending.adjusted = pending.adjusted.saturating_add(minutes.saturating_mul(60));
+                }
+            }
+            Event::Paused => {
+                if let Some(pending) = &mut current {
+                    if pending.paused_at.is_none() {
+                        pending.paused_at = Some(time);
+                        pending.pauses += 1;
+                    }
+                }
+            }
+            Event::Resumed => {
+                if let Some(pending) = &mut current {
+                    if let Some(paused_at) = pending.paused_at.take() {
+                        pending.paused += duration_between(paused_at, time);
+                    }
+                }
+            }
+            Event::Voided => {
+                if let Some(pending) = &mut current {
+                    pending.planned = Duration::from_secs(
+                        pending
+                            .planned
+                            .as_secs()
+                            .saturating_add_signed(-pending.adjusted),
+                    );
+                    pending.started_at = time;
+                    // Waiting to be resumed, which is not a pause of its own
+                    pending.paused_at = Some(time);
+                    pending.paused = Duration::ZERO;
+                    pending.pauses = 0;
+                    pending.adjusted = 0;
+                }
+            }
+            Event::Saved => {
+                if let Some(pending) = &mut current {
+                    pending.saved = true;
+                }
+            }
+            Event::AppQuit if current.as_ref().is_some_and(|pending| pending.saved) => {
+                // Restored paused on the next start
+                if let Some(pending) = &mut current {
+                    pending.paused_at.get_or_insert(time);
+                }
+            }
+            Event::Skipped | Event::Interrupted | Event::AppQuit => {
+                if let Some(pending) = current.take() {
+                    import.records.push(pending.into_record(time, false, None));
+                }
+            }
+            Event::AppStarted => {
+                if let Some(pending) = current.as_mut().filter(|pending| pending.saved) {
+                    pending.saved = false;
+                } else if let (Some(pending), Some(last_seen)) = (current.take(), last_seen) {
+                    // The previous run ended without quitting; its last line is
+                    // the best guess for when the session stopped.
+                    import
+                        .records
+                        .push(pending.into_record(last_seen, false, None));
+                }
+            }
+            Event::Other => {}
+        }
+        last_seen = Some(time);
+    }
+
+    if let Some((pending, next_start)) = replaced {
+        let ended_at = next_start.min(pending.started_at + pending.planned);
+        import
+            .records
+            .push(pending.into_record(ended_at, false, None));
+    }
+    import.unfinished = current.is_some();
+    import
+}
+
+/// Split a log line into its time and session event.
+fn parse_line(line: &str) -> Option<(DateTime<Local>, Event)> {
+    let (time, rest) = line.split_once(' ')?;
+    let time = NaiveDateTime::parse_from_str(time, TIME_FORMAT)
+        .ok()?
+        .and_utc()
+        .with_timezone(&Local);
+    let (level, message) = rest.strip_prefix('[')?.split_once("] ")?;
+
+    let event = if level == "INFO" {
+        parse_message(message)?
+    } else {
+        Event::Other
+    };
+    Some((time, event))
+}
+
+fn parse_message(message: &str) -> Option<Event> {
+    if let Some(rest) = message.strip_prefix("Started ") {
+        let (kind, minutes) = rest.split_once(" session (")?;
+        let minutes = minutes.strip_suffix(" minutes)")?.parse().ok()?;
+        return Some(Event::Started(kind.parse().ok()?, minutes));
+    }
+    if let Some(rest) = message.strip_prefix("Adjusted ") {
+        let (_, change) = rest.split_once(" session by ")?;
+        let (minutes, planned) = change.split_once(" minutes, now ")?;
+        let planned = planned.strip_suffix(" minutes")?.parse().ok()?;
+        return Some(Event::Adjusted(minutes.parse().ok()?, planned));
+    }
+    if let Some(rest) = message.strip_prefix("Completed ") {
+        let (_, name) = rest.split_once(" session")?;
+        // The session type is not used: older versions logged the type of the
+        // session that followed instead of the completed one.
+        let name = match name.strip_prefix(": ") {
+            Some(name) => Some(name.to_string()),
+            None if name.is_empty() => None,
+            None => return None,
+        };
+        return Some(Event::Completed(name));
+    }
+    if message.starts_with("App quit") {
+        return Some(Event::AppQuit);
+    }
+    if message.starts_with("App started") {
+        return Some(Event::AppStarted);
+    }
+    if message.starts_with("Saved the timer to ") {
+        return Some(Event::Saved);
+    }
+    if message.starts_with("Voided ") {
+        return Some(Event::Voided);
+    }
+    // An undo does not say which session it brought back, so the session the
+    // reset or skip ended stays recorded as such.
+    if message.starts_with("Undid ")
+        || message.starts_with("Goal reached: ")
+        || message.starts_with("Received ")
+        || message.starts_with("Restored the timer ")
+        || message == "Suspending"
+        || message == "Resuming"
+    {
+        return Some(Event::Other);
+    }
+
+    let (action, _) = message.strip_suffix(" session")?.split_once(' ')?;
+    match action {
+        "Paused" => Some(Event::Paused),
+        "Resumed" => Some(Event::Resumed),
+        "Skipped" => Some(Event::Skipped),
+        "Interrupted" => Some(Event::Interrupted),
+        _ => None,
+    }
+}
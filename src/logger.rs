@@ -1,54 +1,386 @@
-use simplelog::{ConfigBuilder, LevelFilter, WriteLogger};
-use std::fs::OpenOptions;
+use chrono::{DateTime, Local, NaiveDate, SecondsFormat};
+use serde::Serialize;
+use simplelog::{ConfigBuilder, WriteLogger};
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 
-pub fn init_logger() -> Result<(), Box<dyn std::error::Error>> {
-    let log_file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("tomat.log")?;
+use crate::config::{LogConfig, LogLevel, LogRotation};
+use crate::goals::GoalAttainment;
+use crate::history::SessionKind;
+use crate::paths;
+use crate::timer::TimerState;
 
-    let config = ConfigBuilder::new()
+/// The structured event log, when enabled.
+static EVENT_LOG: OnceLock<Mutex<RotatingFile>> = OnceLock::new();
+
+/// The last events, whether or not the event log is enabled, for crash reports.
+static RECENT_EVENTS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// How many events [`recent_events`] keeps.
+const RECENT_EVENTS_KEPT: usize = 20;
+
+/// Location of the log file when none is configured.
+fn default_log_path() -> Option<PathBuf> {
+    paths::state_dir().map(|dir| dir.join("tomat.log"))
+}
+
+/// Location of the event log when none is configured.
+fn default_events_path() -> Option<PathBuf> {
+    paths::state_dir().map(|dir| dir.join("events.jsonl"))
+}
+
+pub fn init_logger(config: &LogConfig) -> Result<(), Box<dyn std::error::Error>> {
+    if config.events {
+        let path = config
+            .events_file
+            .clone()
+            .or_else(default_events_path)
+            .ok_or("no state directory found for the event log")?;
+        let _ = EVENT_LOG.set(Mutex::new(RotatingFile::create(path, config)?));
+    }
+
+    if config.level == LogLevel::Off {
+        return Ok(());
+    }
+
+    let path = config
+        .file
+        .clone()
+        .or_else(default_log_path)
+        .ok_or("no state directory found for the log file")?;
+    let log_file = RotatingFile::create(path, config)?;
+
+    // Times are in UTC, as simplelog does not use the local time by default
+    let logger_config = ConfigBuilder::new()
         .set_time_format("%Y-%m-%dT%H:%M:%S%.3fZ".to_string())
         .build();
 
-    WriteLogger::init(LevelFilter::Info, config, log_file)?;
+    WriteLogger::init(config.level.into(), logger_config, log_file)?;
 
     Ok(())
 }
 
-pub fn log_session_start(session_type: &str, duration_minutes: u32) {
-    info!("Started {session_type} session ({duration_minutes} minutes)");
+/// A change to a session, as written to the event log.
+#[derive(Debug, Serialize)]
+pub struct SessionEvent {
+    /// The session's identifier in the history.
+    #[serde(rename = "session_id")]
+    pub id: u64,
+    pub kind: SessionKind,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    /// Timer state once the event has happened.
+    pub state: TimerState,
+    pub planned_secs: u64,
+    /// Time the session has run, not counting pauses.
+    pub elapsed_secs: u64,
 }
 
-pub fn log_session_complete(session_type: &str, session_name: Option<&str>) {
-    session_name.map_or_else(
-        || {
-            info!("Completed {session_type} session");
-        },
-        |name| {
-            info!("Completed {session_type} session: {name}");
+/// A line of the event log.
+#[derive(Serialize)]
+struct EventLine<'a, T> {
+    time: String,
+    event: &'a str,
+    #[serde(flatten)]
+    fields: T,
+}
+
+/// Append an event to the event log, if it is enabled, and to the recent events.
+fn write_event(event: &str, fields: impl Serialize) {
+    let line = EventLine {
+        time: Local::now().to_rfc3339_opts(SecondsFormat::Millis, false),
+        event,
+        fields,
+    };
+    let Ok(mut line) = serde_json::to_string(&line) else {
+        return;
+    };
+    if let Ok(mut recent) = RECENT_EVENTS.lock() {
+        if recent.len() == RECENT_EVENTS_KEPT {
+            recent.pop_front();
+        }
+        recent.push_back(line.clone());
+    }
+
+    let Some(log) = EVENT_LOG.get() else {
+        return;
+    };
+    line.push('\n');
+    if let Ok(mut file) = log.lock() {
+        let _ = file.write_all(line.as_bytes()).and_then(|()| file.flush());
+    }
+}
+
+/// The last events written, oldest first, as lines of the event log.
+///
+/// Gives nothing rather than waiting when the events are being written, as
+/// it is called from the panic hook.
+pub fn recent_events() -> Vec<String> {
+    RECENT_EVENTS
+        .try_lock()
+        .map(|recent| recent.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// A log file that moves itself aside when it gets too big or a new day starts.
+///
+/// Size rotation renames the log to `tomat.log.1`, shifting older ones up to
+/// the retention limit. Daily rotation renames it after the day it covers,
+/// such as `tomat.log.2026-10-17`, and deletes the oldest ones beyond the limit.
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    /// Day of the messages currently in the file.
+    day: NaiveDate,
+    rotation: LogRotation,
+    max_size: u64,
+    keep: usize,
+    /// Whether the last write ended a line; messages are written in several
+    /// parts and must not be split across files.
+    at_line_start: bool,
+}
+
+impl RotatingFile {
+    /// Open the log at `path` for appending, creating it and its directory.
+    fn create(path: PathBuf, config: &LogConfig) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let metadata = file.metadata()?;
+        let today = Local::now().date_naive();
+        let day = if metadata.len() == 0 {
+            today
+        } else {
+            metadata.modified().map_or(today, |modified| {
+                DateTime::<Local>::from(modified).date_naive()
+            })
+        };
+
+        Ok(Self {
+            path,
+            file,
+            size: metadata.len(),
+            day,
+            rotation: config.rotation,
+            max_size: config.max_size_kb.saturating_mul(1024),
+            keep: config.keep,
+            at_line_start: true,
+        })
+    }
+
+    fn needs_rotation(&self, incoming: usize, today: NaiveDate) -> bool {
+        match self.rotation {
+            LogRotation::Never => false,
+            LogRotation::Size => self.size > 0 && self.size + incoming as u64 > self.max_size,
+            LogRotation::Daily => self.size > 0 && today != self.day,
+        }
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        match self.rotation {
+            LogRotation::Never => return Ok(()),
+            LogRotation::Size => self.rotate_numbered()?,
+            LogRotation::Daily => self.rotate_dated()?,
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+
+    /// Shift `tomat.log.N` to `tomat.log.N+1`, dropping the oldest.
+    fn rotate_numbered(&self) -> io::Result<()> {
+        if self.keep == 0 {
+            return remove_if_exists(&self.path);
+        }
+        remove_if_exists(&rotated_path(&self.path, &self.keep.to_string()))?;
+        for index in (1..self.keep).rev() {
+            let from = rotated_path(&self.path, &index.to_string());
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, &(index + 1).to_string()))?;
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, "1"))
+    }
+
+    /// Rename the log after its day, then delete the oldest dated logs.
+    fn rotate_dated(&self) -> io::Result<()> {
+        let day = self.day.format("%Y-%m-%d").to_string();
+        let mut target = rotated_path(&self.path, &day);
+        let mut attempt = 1;
+        while target.exists() {
+            target = rotated_path(&self.path, &format!("{day}.{attempt}"));
+            attempt += 1;
+        }
+        fs::rename(&self.path, target)?;
+
+        let Some(name) = self.path.file_name() else {
+            return Ok(());
+        };
+        let dir = match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let prefix = format!("{}.", name.to_string_lossy());
+        let mut rotated: Vec<PathBuf> = fs::read_dir(dir)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(|name| name.strip_prefix(&prefix))
+                    .and_then(|suffix| suffix.get(..10))
+                    .is_some_and(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").is_ok())
+            })
+            .collect();
+        rotated.sort();
+        let excess = rotated.len().saturating_sub(self.keep);
+        for path in &rotated[..excess] {
+            remove_if_exists(path)?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let today = Local::now().date_naive();
+        if self.at_line_start && self.needs_rotation(buf.len(), today) {
+            self.rotate()?;
+        }
+        self.day = today;
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        if written > 0 {
+            self.at_line_start = buf[written - 1] == b'\n';
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn rotated_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+pub fn log_session_start(event: &SessionEvent) {
+    info!(
+        "Started {} session ({} minutes)",
+        event.kind.as_str(),
+        event.planned_secs / 60
+    );
+    write_event("session_started", event);
+}
+
+pub fn log_session_complete(event: &SessionEvent) {
+    if event.name.is_empty() {
+        info!("Completed {} session", event.kind.as_str());
+    } else {
+        info!("Completed {} session: {}", event.kind.as_str(), event.name);
+    }
+    write_event("session_completed", event);
+}
+
+pub fn log_session_pause(event: &SessionEvent) {
+    info!("Paused {} session", event.kind.as_str());
+    write_event("session_paused", event);
+}
+
+pub fn log_session_resume(event: &SessionEvent) {
+    info!("Resumed {} session", event.kind.as_str());
+    write_event("session_resumed", event);
+}
+
+pub fn log_session_skip(event: &SessionEvent) {
+    info!("Skipped {} session", event.kind.as_str());
+    write_event("session_skipped", event);
+}
+
+pub fn log_session_interrupt(event: &SessionEvent) {
+    info!("Interrupted {} session", event.kind.as_str());
+    write_event("session_interrupted", event);
+}
+
+/// A session length change, for the event log.
+#[derive(Serialize)]
+struct Adjustment<'a> {
+    #[serde(flatten)]
+    session: &'a SessionEvent,
+    adjusted_secs: i64,
+}
+
+pub fn log_session_adjust(event: &SessionEvent, adjusted_secs: i64) {
+    info!(
+        "Adjusted {} session by {:+} minutes, now {} minutes",
+        event.kind.as_str(),
+        adjusted_secs / 60,
+        event.planned_secs / 60
+    );
+    write_event(
+        "session_adjusted",
+        Adjustment {
+            session: event,
+            adjusted_secs,
         },
     );
 }
 
-pub fn log_session_pause(session_type: &str) {
-    info!("Paused {session_type} session");
+pub fn log_session_void(event: &SessionEvent) {
+    info!("Voided {} session", event.kind.as_str());
+    write_event("session_voided", event);
 }
 
-pub fn log_session_resume(session_type: &str) {
-    info!("Resumed {session_type} session");
+pub fn log_undo(action: &str) {
+    info!("Undid {action}");
+    write_event("action_undone", serde_json::json!({ "action": action }));
 }
 
-pub fn log_session_skip(session_type: &str) {
-    info!("Skipped {session_type} session");
+pub fn log_goal_reached(attainment: &GoalAttainment) {
+    info!("Goal reached: {}", attainment.message());
+    write_event("goal_reached", attainment);
 }
 
 pub fn log_app_start(work_duration: u32, break_duration: u32, long_break_duration: u32) {
     info!(
         "App started with work: {work_duration}min, short break: {break_duration}min, long break: {long_break_duration}min"
     );
+    write_event(
+        "app_started",
+        serde_json::json!({
+            "work_minutes": work_duration,
+            "short_break_minutes": break_duration,
+            "long_break_minutes": long_break_duration,
+        }),
+    );
 }
 
-pub fn log_app_quit() {
-    info!("App quit");
+/// Log the end of the interface, and the signal that asked for it, if any.
+pub fn log_app_quit(signal: Option<&str>) {
+    if let Some(signal) = signal {
+        info!("App quit on {signal}");
+        write_event("app_quit", serde_json::json!({ "signal": signal }));
+    } else {
+        info!("App quit");
+        write_event("app_quit", serde_json::json!({}));
+    }
 }
@@ -4,68 +4,572 @@
 #[macro_use]
 extern crate log;
 
-use clap::Parser;
-use std::io;
+use chrono::NaiveDate;
+use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand};
+use crossterm::event::{Event, EventStream, KeyEventKind, MouseEventKind};
+use futures_util::StreamExt;
+use ratatui::{layout::Rect, TerminalOptions, Viewport};
+use signals::Signals;
+use snapshot::Snapshot;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
 
+mod action;
 mod app;
+mod completion;
+mod config;
+mod crash;
 mod data;
+mod export;
+mod goals;
+mod history;
+mod import;
+mod import_log;
 mod logger;
+mod palette;
+mod paths;
+mod run;
+mod signals;
+mod snapshot;
+mod streaks;
+mod tags;
 mod timer;
 mod ui;
 
 use app::App;
+use config::Config;
+use history::SessionKind;
+use tags::LabelFilter;
 
 #[derive(Parser)]
 #[command(name = "tomat")]
 #[command(about = "A terminal-based Pomodoro technique timer")]
 struct Cli {
-    #[arg(short, long, default_value_t = 25)]
-    work: u32,
+    /// Work session length in minutes [default: 25, or the configuration]
+    #[arg(short, long, value_name = "MINUTES", value_parser = clap::value_parser!(u32).range(1..))]
+    work: Option<u32>,
 
-    #[arg(short, long, default_value_t = 5)]
-    short_break: u32,
+    /// Short break length in minutes [default: 5, or the configuration]
+    #[arg(short, long, value_name = "MINUTES", value_parser = clap::value_parser!(u32).range(1..))]
+    short_break: Option<u32>,
 
-    #[arg(short, long, default_value_t = 15)]
-    long_break_time: u32,
+    /// Long break length in minutes [default: 15, or the configuration]
+    #[arg(short, long, value_name = "MINUTES", value_parser = clap::value_parser!(u32).range(1..))]
+    long_break_time: Option<u32>,
+
+    /// Render in a few lines at the bottom of the shell instead of the full screen
+    #[arg(long, value_name = "HEIGHT", num_args = 0..=1, default_missing_value = "3")]
+    inline: Option<u16>,
+
+    /// Daily goal in completed pomodoros (overrides the configuration file)
+    #[arg(long, value_name = "POMODOROS")]
+    daily_goal: Option<u32>,
+
+    /// Weekly goal in focused hours (overrides the configuration file)
+    #[arg(long, value_name = "HOURS")]
+    weekly_goal: Option<f64>,
+
+    /// Write the log to this file instead of the state directory
+    #[arg(long, value_name = "PATH", global = true)]
+    log_file: Option<PathBuf>,
+
+    /// Most verbose log messages to write; `off` disables the log file
+    #[arg(long, value_enum, value_name = "LEVEL", global = true)]
+    log_level: Option<config::LogLevel>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a single session in the foreground and exit when it is done
+    ///
+    /// Exits with status 130 if the session is interrupted, so it can be chained
+    /// in scripts, e.g. `tomat run 25 && git commit`.
+    Run(RunArgs),
+
+    /// Export the recorded sessions as CSV, JSON or iCalendar
+    Export(ExportArgs),
+
+    /// Add sessions exported by another tool to the history
+    Import(ImportArgs),
+
+    /// Add the sessions found in an old tomat.log file to the history
+    ImportLog(ImportLogArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Session type and/or length in minutes, e.g. `25`, `break 5` or `long-break`
+    #[arg(value_name = "TYPE|MINUTES", num_args = 0..=2)]
+    spec: Vec<String>,
+
+    /// Run a work session of the given length in minutes
+    #[arg(long, value_name = "MINUTES", conflicts_with_all = ["spec", "break_minutes", "long_break"])]
+    work: Option<u32>,
+
+    /// Run a short break of the given length in minutes
+    #[arg(long = "break", value_name = "MINUTES", conflicts_with_all = ["spec", "long_break"])]
+    break_minutes: Option<u32>,
+
+    /// Run a long break of the given length in minutes
+    #[arg(long, value_name = "MINUTES", conflicts_with = "spec")]
+    long_break: Option<u32>,
+
+    /// Name of the session, as recorded in the history
+    #[arg(short, long, default_value = "")]
+    name: String,
+
+    /// Do not show the progress line or ring the bell
+    #[arg(short, long)]
+    quiet: bool,
+}
+
+#[derive(Args)]
+struct ExportArgs {
+    /// First day to export (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    from: Option<NaiveDate>,
+
+    /// Last day to export, inclusive (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    to: Option<NaiveDate>,
+
+    /// Output format; iCalendar output only contains work sessions
+    #[arg(short, long, value_enum, default_value_t = export::ExportFormat::Csv)]
+    format: export::ExportFormat,
+
+    /// Only export sessions whose name contains this text
+    #[arg(short, long)]
+    name: Option<String>,
+
+    /// Only export sessions of this type (work, break or long-break)
+    #[arg(long = "type", value_name = "TYPE")]
+    kind: Option<SessionKind>,
+
+    /// Only export sessions of this project
+    #[arg(short, long)]
+    project: Option<String>,
+
+    /// Only export sessions with this tag; repeat to require several
+    #[arg(short, long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+
+    /// Write to this file instead of standard output
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+impl ExportArgs {
+    /// Export the history, reading it from its default location.
+    fn run(self) -> io::Result<ExitCode> {
+        let filter = export::ExportFilter {
+            from: self.from,
+            to: self.to,
+            name: self.name,
+            kind: self.kind,
+            labels: self
+                .project
+                .iter()
+                .map(|project| LabelFilter::Project(tags::normalize(project)))
+                .chain(
+                    self.tags
+                        .iter()
+                        .map(|tag| LabelFilter::Tag(tags::normalize(tag))),
+                )
+                .collect(),
+        };
+        let history = history::History::open_default();
+
+        let mut out: Box<dyn Write> = match &self.output {
+            Some(path) => match File::create(path) {
+                Ok(file) => Box::new(BufWriter::new(file)),
+                Err(e) => {
+                    eprintln!("tomat: cannot write {}: {e}", path.display());
+                    return Ok(ExitCode::FAILURE);
+                }
+            },
+            None => Box::new(BufWriter::new(io::stdout().lock())),
+        };
+        export::export(history.records(), &filter, self.format, &mut out)?;
+        out.flush()?;
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+#[derive(Args)]
+struct ImportArgs {
+    /// Format of the file to import
+    #[arg(long = "from", value_enum, value_name = "FORMAT")]
+    format: import::ImportFormat,
+
+    /// File to import
+    #[arg(value_name = "PATH")]
+    path: PathBuf,
+
+    /// Column holding a field, for --from csv, e.g. `start="Start Time"`
+    ///
+    /// Fields are start, end, duration, planned, name, project, tags, type,
+    /// completed, note and rating. By default the columns written by `tomat export --format csv` are used.
+    #[arg(short, long, value_name = "FIELD=HEADER")]
+    column: Vec<String>,
+
+    /// Report what would be imported without changing the history
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl ImportArgs {
+    /// Import the sessions, skipping those that overlap the history.
+    fn run(&self) -> Result<ExitCode, String> {
+        let importer = self.format.importer(&self.column)?;
+        let content = fs::read_to_string(&self.path)
+            .map_err(|e| format!("cannot read {}: {e}", self.path.display()))?;
+        let parsed = importer.parse(&content)?;
+
+        for (line, reason) in &parsed.skipped {
+            eprintln!("tomat: skipping line {line}: {reason}");
+        }
+
+        let mut history = history::History::open_default();
+        let summary = import::add_to_history(&mut history, parsed.records, self.dry_run)
+            .map_err(|e| format!("cannot write the history: {e}"))?;
+
+        let verb = if self.dry_run {
+            "Would import"
+        } else {
+            "Imported"
+        };
+        println!(
+            "{verb} {} sessions from {} ({} already in the history, {} skipped lines)",
+            summary.imported,
+            self.path.display(),
+            summary.duplicates,
+            parsed.skipped.len()
+        );
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+#[derive(Args)]
+struct ImportLogArgs {
+    /// Log file to read, as written by earlier versions of tomat
+    #[arg(value_name = "PATH")]
+    path: PathBuf,
+
+    /// Report what would be imported without changing the history
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl ImportLogArgs {
+    /// Import the sessions, skipping those that are already in the history.
+    fn run(&self) -> io::Result<ExitCode> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("tomat: cannot read {}: {e}", self.path.display());
+                return Ok(ExitCode::FAILURE);
+            }
+        };
+        let import = import_log::parse_log(&content);
+
+        for (line, text) in &import.unparsable {
+            eprintln!("tomat: skipping unparsable line {line}: {text}");
+        }
+        if import.unfinished {
+            eprintln!("tomat: skipping the session still running at the end of the log");
+        }
+
+        let mut history = history::History::open_default();
+        let summary = import::add_to_history(&mut history, import.records, self.dry_run)?;
+
+        let verb = if self.dry_run {
+            "Would import"
+        } else {
+            "Imported"
+        };
+        println!(
+            "{verb} {} sessions from {} ({} already in the history, {} unparsable lines)",
+            summary.imported,
+            self.path.display(),
+            summary.duplicates,
+            import.unparsable.len()
+        );
+        Ok(ExitCode::SUCCESS)
+    }
 }
 
-fn main() -> io::Result<()> {
-    let cli = Cli::parse();
+impl RunArgs {
+    /// Resolve the session to run, defaulting durations to the timer settings.
+    fn options(&self, timer: &config::TimerConfig) -> Result<run::RunOptions, String> {
+        let (kind, minutes) = if let Some(minutes) = self.work {
+            (SessionKind::Work, Some(minutes))
+        } else if let Some(minutes) = self.break_minutes {
+            (SessionKind::ShortBreak, Some(minutes))
+        } else if let Some(minutes) = self.long_break {
+            (SessionKind::LongBreak, Some(minutes))
+        } else {
+            let mut kind = SessionKind::Work;
+            let mut minutes = None;
+            for token in &self.spec {
+                if let Ok(value) = token.parse::<u32>() {
+                    if minutes.replace(value).is_some() {
+                        return Err("the session length was given twice".to_string());
+                    }
+                } else {
+                    kind = token.parse()?;
+                }
+            }
+            (kind, minutes)
+        };
+
+        let minutes = minutes.unwrap_or(match kind {
+            SessionKind::Work => timer.work_minutes,
+            SessionKind::ShortBreak => timer.short_break_minutes,
+            SessionKind::LongBreak => timer.long_break_minutes,
+        });
+        if minutes == 0 {
+            return Err("the session length must be at least one minute".to_string());
+        }
+
+        Ok(run::RunOptions {
+            kind,
+            minutes,
+            name: self.name.trim().to_string(),
+            quiet: self.quiet,
+        })
+    }
+}
+
+fn main() -> io::Result<ExitCode> {
+    let mut cli = Cli::parse();
+
+    let mut config = Config::load_default();
+    if let Some(work) = cli.work {
+        config.timer.work_minutes = work;
+    }
+    if let Some(short_break) = cli.short_break {
+        config.timer.short_break_minutes = short_break;
+    }
+    if let Some(long_break) = cli.long_break_time {
+        config.timer.long_break_minutes = long_break;
+    }
+    if let Some(daily_goal) = cli.daily_goal {
+        config.goals.daily_pomodoros = Some(daily_goal);
+    }
+    if let Some(weekly_goal) = cli.weekly_goal {
+        config.goals.weekly_hours = Some(weekly_goal);
+    }
+    if let Some(log_file) = cli.log_file.take() {
+        config.log.file = Some(log_file);
+    }
+    if let Some(log_level) = cli.log_level {
+        config.log.level = log_level;
+    }
 
     // Initialize logger
-    if let Err(e) = logger::init_logger() {
+    if let Err(e) = logger::init_logger(&config.log) {
         eprintln!("Failed to initialize logger: {e}");
     }
 
-    logger::log_app_start(cli.work, cli.short_break, cli.long_break_time);
+    match cli.command.take() {
+        Some(Command::Run(args)) => {
+            let options = args
+                .options(&config.timer)
+                .unwrap_or_else(|e| Cli::command().error(ErrorKind::InvalidValue, e).exit());
+            return run::run(&options, &config);
+        }
+        Some(Command::Export(args)) => {
+            if let (Some(from), Some(to)) = (args.from, args.to) {
+                if from > to {
+                    Cli::command()
+                        .error(ErrorKind::InvalidValue, "--from must not be after --to")
+                        .exit();
+                }
+            }
+            return args.run();
+        }
+        Some(Command::Import(args)) => {
+            return Ok(args.run().unwrap_or_else(|e| {
+                eprintln!("tomat: {e}");
+                ExitCode::FAILURE
+            }));
+        }
+        Some(Command::ImportLog(args)) => return args.run(),
+        None => {}
+    }
+
+    logger::log_app_start(
+        config.timer.work_minutes,
+        config.timer.short_break_minutes,
+        config.timer.long_break_minutes,
+    );
 
-    let mut terminal = ratatui::init();
+    let mut app = App::new(config);
+    if let Some(snapshot) = Snapshot::take() {
+        app.restore(snapshot);
+    }
+
+    let mut terminal = cli.inline.map_or_else(ratatui::init, |height| {
+        app.enable_inline_mode();
+        ratatui::init_with_options(TerminalOptions {
+            viewport: Viewport::Inline(height.max(1)),
+        })
+    });
     terminal.clear()?;
+    // Inline mode shares the screen with the shell, whose scrollback keeps the wheel
+    let mouse = app.config.ui.mouse && !app.inline;
+    enable_input_features(mouse);
+    crash::install(&app.config, mouse);
 
-    let mut app = App::new(cli.work, cli.short_break, cli.long_break_time);
-    let result = run_app(&mut terminal, &mut app);
+    let result = run_app(&mut terminal, &mut app, mouse);
+    disable_input_features(mouse);
+    // Logged first, as the terminal may be gone after a hangup
+    logger::log_app_quit(app.quit_signal);
 
-    ratatui::restore();
+    if app.inline {
+        // Replace the viewport with a plain summary line that stays in the scrollback.
+        // The alternate screen was never entered, so only raw mode needs undoing.
+        terminal.clear()?;
+        terminal.show_cursor()?;
+        crossterm::terminal::disable_raw_mode()?;
+        let timer_data = ui::timer_data(&app);
+        let name = if timer_data.session_name.is_empty() {
+            String::new()
+        } else {
+            format!(" · {}", timer_data.session_name)
+        };
+        println!(
+            "🍅 {}{name} · {} sessions completed",
+            timer_data.compact_line(),
+            timer_data.sessions_completed
+        );
+    } else {
+        ratatui::restore();
+    }
 
-    logger::log_app_quit();
+    result.map(|()| ExitCode::SUCCESS)
+}
+
+/// Turn on the terminal features the interface uses on top of raw mode.
+fn enable_input_features(mouse: bool) {
+    // Pasted text arrives in one piece instead of as a burst of key presses
+    let _ = crossterm::execute!(io::stdout(), crossterm::event::EnableBracketedPaste);
+    if mouse {
+        let _ = crossterm::execute!(io::stdout(), crossterm::event::EnableMouseCapture);
+    }
+}
 
-    result
+fn disable_input_features(mouse: bool) {
+    let _ = crossterm::execute!(io::stdout(), crossterm::event::DisableBracketedPaste);
+    if mouse {
+        let _ = crossterm::execute!(io::stdout(), crossterm::event::DisableMouseCapture);
+    }
 }
 
-fn run_app(terminal: &mut ratatui::DefaultTerminal, app: &mut App) -> io::Result<()> {
+/// Give the terminal back to the shell, and take it over again once the
+/// process is continued with `fg`. The timer keeps running meanwhile.
+fn suspend(terminal: &mut ratatui::DefaultTerminal, inline: bool, mouse: bool) -> io::Result<()> {
+    info!("Suspending");
+    disable_input_features(mouse);
+    if !inline {
+        crossterm::execute!(io::stdout(), crossterm::terminal::LeaveAlternateScreen)?;
+    }
+    terminal.show_cursor()?;
+    crossterm::terminal::disable_raw_mode()?;
+
+    signals::stop_process()?;
+
+    info!("Resuming");
+    crossterm::terminal::enable_raw_mode()?;
+    if !inline {
+        crossterm::execute!(io::stdout(), crossterm::terminal::EnterAlternateScreen)?;
+    }
+    enable_input_features(mouse);
+    terminal.clear()
+}
+
+fn run_app(terminal: &mut ratatui::DefaultTerminal, app: &mut App, mouse: bool) -> io::Result<()> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(event_loop(terminal, app, mouse))
+}
+
+/// Wait for terminal events and ticks, redrawing only after something changed.
+// The screens are not `Send`, which the current-thread runtime does not need
+#[allow(clippy::future_not_send)]
+async fn event_loop(
+    terminal: &mut ratatui::DefaultTerminal,
+    app: &mut App,
+    mouse: bool,
+) -> io::Result<()> {
+    let mut events = EventStream::new();
+    let mut signals = Signals::new()?;
+    let mut dirty = true;
     loop {
-        terminal.draw(|frame| ui::render(frame, app))?;
+        if dirty {
+            crash::record(app.snapshot());
+            terminal.draw(|frame| ui::render(frame, app))?;
+            dirty = false;
+        }
 
-        if crossterm::event::poll(std::time::Duration::from_millis(100))? {
-            if let crossterm::event::Event::Key(key) = crossterm::event::read()? {
-                // Only handle key press events, ignore key release
-                if key.kind == crossterm::event::KeyEventKind::Press && app.handle_key(key) {
+        tokio::select! {
+            event = events.next() => {
+                let event = match event {
+                    Some(Ok(event)) => event,
+                    // The terminal is gone, as after a hangup
+                    Some(Err(e)) => {
+                        app.save_snapshot();
+                        return Err(e);
+                    }
+                    None => {
+                        app.save_snapshot();
+                        break;
+                    }
+                };
+                // Bring the timer up to date before the event acts on it
+                app.tick();
+                match event {
+                    // Only handle key press events, ignore key release
+                    Event::Key(key) if key.kind == KeyEventKind::Press => {
+                        if app.handle_key(key) {
+                            break;
+                        }
+                    }
+                    Event::Paste(text) => app.handle_paste(&text),
+                    Event::Mouse(mouse) if mouse.kind != MouseEventKind::Moved => {
+                        let size = terminal.size()?;
+                        app.handle_mouse(mouse, Rect::new(0, 0, size.width, size.height));
+                    }
+                    Event::Resize(..) => {}
+                    _ => continue,
+                }
+                dirty = true;
+            }
+            signal = signals.recv() => {
+                app.tick();
+                app.handle_signal(signal);
+                if app.should_quit {
                     break;
                 }
+                dirty = true;
+            }
+            () = tokio::time::sleep(app.until_next_tick()) => {
+                app.tick();
+                dirty = true;
             }
         }
 
-        app.tick();
+        if std::mem::take(&mut app.suspend_requested) {
+            suspend(terminal, app.inline, mouse)?;
+            app.tick();
+            dirty = true;
+        }
     }
 
     Ok(())
@@ -0,0 +1,76 @@
+//! The command palette, to find any action by typing part of its name.
+//!
+//! Actions are matched like session names (see [`fuzzy_match`]); with
+//! nothing typed, all of them are listed in their usual order.
+
+use std::cmp::Reverse;
+
+use crate::action::Action;
+use crate::completion::{fuzzy_match, Suggestion};
+use crate::ui::text_input::TextInput;
+
+/// An action matching what was typed in the palette.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteMatch {
+    pub action: Action,
+    /// The action's title, with the characters matching the input.
+    pub title: Suggestion,
+}
+
+/// State of the open command palette.
+#[derive(Debug)]
+pub struct Palette {
+    pub input: TextInput,
+    pub matches: Vec<PaletteMatch>,
+    pub selected: usize,
+}
+
+impl Palette {
+    pub fn new() -> Self {
+        let mut palette = Self {
+            input: TextInput::default(),
+            matches: Vec::new(),
+            selected: 0,
+        };
+        palette.update();
+        palette
+    }
+
+    /// Refresh the matching actions after the input changed.
+    pub fn update(&mut self) {
+        let input = self.input.value().trim();
+        let mut matches: Vec<(u8, PaletteMatch)> = Action::all()
+            .into_iter()
+            .filter_map(|action| {
+                let name = action.title();
+                let (quality, matched) = fuzzy_match(&name, input)?;
+                Some((
+                    quality,
+                    PaletteMatch {
+                        action,
+                        title: Suggestion { name, matched },
+                    },
+                ))
+            })
+            .collect();
+        // Stable, so actions of the same quality keep their usual order
+        matches.sort_by_key(|(quality, _)| Reverse(*quality));
+        self.matches = matches.into_iter().map(|(_, found)| found).collect();
+        self.selected = 0;
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.matches.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// The highlighted action, if any matches.
+    pub fn selection(&self) -> Option<Action> {
+        self.matches.get(self.selected).map(|found| found.action)
+    }
+}
@@ -0,0 +1,38 @@
+//! Locations of the files tomat keeps between runs.
+//!
+//! Paths follow the XDG base directory specification, falling back to the
+//! conventional directories under `$HOME` when the variables are unset.
+
+use std::env;
+use std::path::PathBuf;
+
+/// Resolve an XDG base directory, or `$HOME` joined with the fallback components.
+fn base_dir(var: &str, fallback: &[&str]) -> Option<PathBuf> {
+    env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| {
+            env::var_os("HOME")
+                .or_else(|| env::var_os("USERPROFILE"))
+                .map(|home| {
+                    fallback
+                        .iter()
+                        .fold(PathBuf::from(home), |path, part| path.join(part))
+                })
+        })
+}
+
+/// Directory holding tomat's persistent data, such as the session history.
+pub fn data_dir() -> Option<PathBuf> {
+    base_dir("XDG_DATA_HOME", &[".local", "share"]).map(|dir| dir.join("tomat"))
+}
+
+/// Directory holding tomat's configuration file.
+pub fn config_dir() -> Option<PathBuf> {
+    base_dir("XDG_CONFIG_HOME", &[".config"]).map(|dir| dir.join("tomat"))
+}
+
+/// Directory holding tomat's state that is not worth backing up, such as logs.
+pub fn state_dir() -> Option<PathBuf> {
+    base_dir("XDG_STATE_HOME", &[".local", "state"]).map(|dir| dir.join("tomat"))
+}
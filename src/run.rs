@@ -0,0 +1,180 @@
+//! One-shot sessions for `tomat run`.
+//!
+//! Runs a single session in the foreground without the TUI, which makes tomat
+//! usable from scripts such as `tomat run 25 && git commit`.
+
+use chrono::Local;
+use std::io::{self, IsTerminal, Write};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::goals::{GoalLog, GoalProgress};
+use crate::history::{History, SessionKind, SessionRecord};
+use crate::logger;
+use crate::timer::TimerState;
+
+/// Exit code used when the session is interrupted before it completes,
+/// following the shell convention for SIGINT.
+pub const EXIT_INTERRUPTED: u8 = 130;
+
+/// Width of the progress bar in the progress line.
+const BAR_WIDTH: usize = 20;
+
+/// What to run, resolved from the command line.
+pub struct RunOptions {
+    pub kind: SessionKind,
+    pub minutes: u32,
+    pub name: String,
+    pub quiet: bool,
+}
+
+/// Run the session until it completes or is interrupted.
+///
+/// Returns success when the session ran to completion and [`EXIT_INTERRUPTED`]
+/// otherwise. The session is recorded to the history either way.
+pub fn run(options: &RunOptions, config: &Config) -> io::Result<ExitCode> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(run_session(options, config))
+}
+
+async fn run_session(options: &RunOptions, config: &Config) -> io::Result<ExitCode> {
+    let planned = Duration::from_secs(u64::from(options.minutes) * 60);
+    let show_progress = !options.quiet && io::stderr().is_terminal();
+    let started_at = Local::now();
+    let start = Instant::now();
+
+    let event = |state, elapsed: Duration| logger::SessionEvent {
+        id: SessionRecord::id_for(&started_at),
+        kind: options.kind,
+        name: options.name.clone(),
+        state,
+        planned_secs: planned.as_secs(),
+        elapsed_secs: elapsed.as_secs(),
+    };
+    logger::log_session_start(&event(options.kind.running_state(), Duration::ZERO));
+
+    let mut ticker = tokio::time::interval(Duration::from_secs(1));
+    // Listen for the whole session, so a signal between two ticks is not missed
+    let interrupted = interrupted();
+    tokio::pin!(interrupted);
+    let completed = loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                let elapsed = start.elapsed();
+                if show_progress {
+                    print_progress(options, elapsed.min(planned), planned)?;
+                }
+                if elapsed >= planned {
+                    break true;
+                }
+            }
+            () = &mut interrupted => break false,
+        }
+    };
+
+    if show_progress {
+        eprintln!();
+    }
+
+    let actual = start.elapsed().min(planned);
+    let mut history = History::open_default();
+    let record = SessionRecord::new(
+        options.kind,
+        &options.name,
+        started_at,
+        Local::now(),
+        planned,
+        actual,
+        completed,
+    );
+    if let Err(e) = history.append(record) {
+        error!("Failed to record session: {e}");
+        if !options.quiet {
+            eprintln!("tomat: failed to record session: {e}");
+        }
+    }
+
+    let ended = event(TimerState::NotStarted, actual);
+    if completed {
+        logger::log_session_complete(&ended);
+        if !options.quiet {
+            // Ring the terminal bell as the completion notification
+            eprint!("\x07");
+        }
+        if options.kind == SessionKind::Work {
+            celebrate_goals(&history, config, options.quiet);
+        }
+        Ok(ExitCode::SUCCESS)
+    } else {
+        logger::log_session_interrupt(&ended);
+        Ok(ExitCode::from(EXIT_INTERRUPTED))
+    }
+}
+
+/// Log goals reached by this session and announce them on stderr.
+fn celebrate_goals(history: &History, config: &Config, quiet: bool) {
+    let progress = GoalProgress::compute(&config.goals, history, Local::now().date_naive());
+    for reached in GoalLog::open_default().record_reached(&progress) {
+        logger::log_goal_reached(&reached);
+        if config.goals.celebrate && !quiet {
+            eprintln!("{}", reached.message());
+        }
+    }
+}
+
+/// Redraw the progress line on stderr.
+fn print_progress(options: &RunOptions, elapsed: Duration, planned: Duration) -> io::Result<()> {
+    let remaining = planned.saturating_sub(elapsed).as_secs();
+    let ratio = if planned.is_zero() {
+        1.0
+    } else {
+        elapsed.as_secs_f64() / planned.as_secs_f64()
+    };
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let filled = ((ratio * BAR_WIDTH as f64) as usize).min(BAR_WIDTH);
+
+    let mut stderr = io::stderr().lock();
+    write!(
+        stderr,
+        "\r🍅 {:02}:{:02} {} [{}{}] {:>3.0}%",
+        remaining / 60,
+        remaining % 60,
+        options.kind.as_str(),
+        "#".repeat(filled),
+        "-".repeat(BAR_WIDTH - filled),
+        ratio * 100.0
+    )?;
+    stderr.flush()
+}
+
+/// Resolve once the process is asked to stop (Ctrl+C, or SIGTERM on Unix).
+async fn interrupted() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+            }
+            Err(_) => {
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
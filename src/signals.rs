@@ -0,0 +1,93 @@
+//! Signals sent to the running timer, for a clean exit and for scripts.
+//!
+//! SIGTERM, SIGHUP and SIGINT save the timer and quit, SIGTSTP suspends the
+//! interface like Ctrl+Z, SIGUSR1 starts, pauses or resumes the timer and
+//! SIGUSR2 skips to the next session, so `pkill -USR1 tomat` works from a
+//! script or a key binding. Only Ctrl+C (SIGINT) is handled on other platforms.
+
+use std::io;
+
+#[cfg(unix)]
+use tokio::signal::unix::{signal, Signal, SignalKind};
+
+/// What a received signal asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalEvent {
+    /// Save the timer and quit, naming the signal for the log.
+    Quit(&'static str),
+    Suspend,
+    TogglePause,
+    Skip,
+}
+
+/// The signals tomat listens to while the interface runs.
+#[cfg(unix)]
+pub struct Signals {
+    terminate: Signal,
+    hangup: Signal,
+    interrupt: Signal,
+    stop: Signal,
+    user1: Signal,
+    user2: Signal,
+}
+
+#[cfg(unix)]
+impl Signals {
+    pub fn new() -> io::Result<Self> {
+        Ok(Self {
+            terminate: signal(SignalKind::terminate())?,
+            hangup: signal(SignalKind::hangup())?,
+            interrupt: signal(SignalKind::interrupt())?,
+            stop: signal(SignalKind::from_raw(signal_hook::consts::SIGTSTP))?,
+            user1: signal(SignalKind::user_defined1())?,
+            user2: signal(SignalKind::user_defined2())?,
+        })
+    }
+
+    /// Wait for the next signal.
+    pub async fn recv(&mut self) -> SignalEvent {
+        tokio::select! {
+            _ = self.terminate.recv() => SignalEvent::Quit("SIGTERM"),
+            _ = self.hangup.recv() => SignalEvent::Quit("SIGHUP"),
+            _ = self.interrupt.recv() => SignalEvent::Quit("SIGINT"),
+            _ = self.stop.recv() => SignalEvent::Suspend,
+            _ = self.user1.recv() => SignalEvent::TogglePause,
+            _ = self.user2.recv() => SignalEvent::Skip,
+        }
+    }
+}
+
+/// The signals tomat listens to while the interface runs.
+#[cfg(not(unix))]
+pub struct Signals;
+
+#[cfg(not(unix))]
+impl Signals {
+    #[allow(clippy::unnecessary_wraps)]
+    pub const fn new() -> io::Result<Self> {
+        Ok(Self)
+    }
+
+    /// Wait for the next signal.
+    pub async fn recv(&mut self) -> SignalEvent {
+        if tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+        SignalEvent::Quit("Ctrl+C")
+    }
+}
+
+/// Stop the process as the shell's job control expects, returning once it is
+/// continued with `fg` or SIGCONT.
+#[cfg(unix)]
+pub fn stop_process() -> io::Result<()> {
+    // SIGTSTP is caught above, so stop with the signal that cannot be
+    signal_hook::low_level::raise(signal_hook::consts::SIGSTOP)
+}
+
+/// Suspending is not supported outside Unix.
+#[cfg(not(unix))]
+#[allow(clippy::unnecessary_wraps)]
+pub const fn stop_process() -> io::Result<()> {
+    Ok(())
+}
@@ -0,0 +1,65 @@
+//! The timer saved when tomat is stopped from outside, to pick up where it
+//! left off on the next start.
+//!
+//! The snapshot lives in `$XDG_STATE_HOME/tomat/snapshot.json` and is removed
+//! once it has been restored.
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::paths;
+use crate::timer::PomodoroTimer;
+
+/// Everything needed to carry on with the session on the timer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub saved_at: DateTime<Local>,
+    pub timer: PomodoroTimer,
+    pub sessions_completed: u32,
+    pub session_name: String,
+    pub current_session_start: Option<DateTime<Local>>,
+    pub segment_start: Option<DateTime<Local>>,
+    pub segment_pauses: u32,
+    pub segment_adjustment: i64,
+}
+
+impl Snapshot {
+    fn path() -> Option<PathBuf> {
+        paths::state_dir().map(|dir| dir.join("snapshot.json"))
+    }
+
+    /// Write the snapshot, replacing any previous one. Returns where it went.
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let path = Self::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no state directory"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_string(self)?)?;
+        fs::rename(&temp_path, &path)?;
+        Ok(path)
+    }
+
+    /// Read the saved snapshot, if any, and remove it so it is restored once.
+    pub fn take() -> Option<Self> {
+        let path = Self::path()?;
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                error!("Failed to read {}: {e}", path.display());
+                return None;
+            }
+        };
+        if let Err(e) = fs::remove_file(&path) {
+            error!("Failed to remove {}: {e}", path.display());
+        }
+        serde_json::from_str(&content)
+            .map_err(|e| error!("Ignoring invalid snapshot {}: {e}", path.display()))
+            .ok()
+    }
+}
@@ -0,0 +1,80 @@
+//! Streaks of consecutive days with enough focused work.
+//!
+//! A day counts towards a streak when it met the daily goal (as recorded in the
+//! goal log, or judged against the current target), or, without a daily goal,
+//! when at least one pomodoro was completed. Rest days never break a streak.
+
+use chrono::{Datelike, NaiveDate};
+use std::collections::BTreeSet;
+
+use crate::config::Config;
+use crate::data::stats::daily_totals;
+use crate::goals::{GoalKind, GoalLog};
+use crate::history::History;
+
+/// Current and longest streaks, in qualifying days.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Streaks {
+    pub current: u32,
+    pub longest: u32,
+}
+
+impl Streaks {
+    /// Compute the streaks up to and including `today`.
+    ///
+    /// A day that has not qualified yet does not break the current streak
+    /// while it is still today.
+    pub fn compute(
+        config: &Config,
+        history: &History,
+        goal_log: &GoalLog,
+        today: NaiveDate,
+    ) -> Self {
+        let target = config.goals.daily_pomodoros.filter(|target| *target > 0);
+        let mut qualifying: BTreeSet<NaiveDate> = daily_totals(history.records())
+            .into_iter()
+            .filter(|(_, totals)| totals.pomodoros >= target.unwrap_or(1))
+            .map(|(day, _)| day)
+            .collect();
+        if target.is_some() {
+            qualifying.extend(goal_log.periods(GoalKind::Daily));
+        }
+
+        let Some(first) = qualifying.first().copied() else {
+            return Self::default();
+        };
+        let is_rest_day = |day: NaiveDate| config.streaks.rest_days.contains(&day.weekday());
+
+        // Longest streak, walking forward through every day since the first qualifying one
+        let mut longest = 0;
+        let mut run = 0;
+        for day in first.iter_days().take_while(|day| *day <= today) {
+            if qualifying.contains(&day) {
+                run += 1;
+                longest = longest.max(run);
+            } else if !is_rest_day(day) && day != today {
+                run = 0;
+            }
+        }
+
+        // Current streak, walking back from today
+        let mut current = 0;
+        let mut day = Some(today);
+        while let Some(date) = day.filter(|date| *date >= first) {
+            if qualifying.contains(&date) {
+                current += 1;
+            } else if !is_rest_day(date) && date != today {
+                break;
+            }
+            day = date.pred_opt();
+        }
+
+        Self { current, longest }
+    }
+
+    /// Short description such as "3 days (best 10)".
+    pub fn describe(self) -> String {
+        let unit = if self.current == 1 { "day" } else { "days" };
+        format!("{} {unit} (best {})", self.current, self.longest)
+    }
+}
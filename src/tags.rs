@@ -0,0 +1,114 @@
+//! Projects and tags, written inline in session names.
+//!
+//! In `fix login bug +backend #client-x`, `+backend` is the project and
+//! `#client-x` a tag. Projects and tags are lowercased, must start with a
+//! letter (so `issue #42` keeps its number), and a session has at most one
+//! project: when several are given the last one wins.
+
+use std::fmt;
+
+use crate::history::SessionRecord;
+
+/// A session name split into its title, project and tags.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Label {
+    pub name: String,
+    pub project: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl Label {
+    /// Split `text` into the name and the `+project` and `#tag` words.
+    pub fn parse(text: &str) -> Self {
+        let mut label = Self::default();
+        let mut words = Vec::new();
+        for word in text.split_whitespace() {
+            if let Some(project) = marked(word, '+') {
+                label.project = Some(project);
+            } else if let Some(tag) = marked(word, '#') {
+                if !label.tags.contains(&tag) {
+                    label.tags.push(tag);
+                }
+            } else {
+                words.push(word);
+            }
+        }
+        label.name = words.join(" ");
+        label
+    }
+}
+
+/// Write the label back as it would be typed, name first.
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut words: Vec<String> = Vec::new();
+        if !self.name.is_empty() {
+            words.push(self.name.clone());
+        }
+        words.extend(self.project.iter().map(|project| format!("+{project}")));
+        words.extend(self.tags.iter().map(|tag| format!("#{tag}")));
+        write!(f, "{}", words.join(" "))
+    }
+}
+
+/// The project or tag in `word` if it starts with `marker` then a letter.
+fn marked(word: &str, marker: char) -> Option<String> {
+    word.strip_prefix(marker)
+        .filter(|rest| rest.chars().next().is_some_and(char::is_alphabetic))
+        .map(str::to_lowercase)
+}
+
+/// Normalize a project or tag given on the command line or in an import,
+/// accepting it with or without its `+` or `#`. Spaces become dashes.
+pub fn normalize(value: &str) -> String {
+    value
+        .trim_start_matches(|c: char| c == '+' || c == '#' || c.is_whitespace())
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
+
+/// Sessions with a given project or tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LabelFilter {
+    Project(String),
+    Tag(String),
+}
+
+impl LabelFilter {
+    pub fn matches(&self, record: &SessionRecord) -> bool {
+        match self {
+            Self::Project(project) => record.project.as_ref() == Some(project),
+            Self::Tag(tag) => record.tags.contains(tag),
+        }
+    }
+
+    /// Every project then every tag used in `records`, each sorted by name.
+    pub fn all(records: &[SessionRecord]) -> Vec<Self> {
+        let mut projects: Vec<&String> = records
+            .iter()
+            .filter_map(|record| record.project.as_ref())
+            .collect();
+        projects.sort();
+        projects.dedup();
+        let mut tags: Vec<&String> = records.iter().flat_map(|record| &record.tags).collect();
+        tags.sort();
+        tags.dedup();
+
+        projects
+            .into_iter()
+            .map(|project| Self::Project(project.clone()))
+            .chain(tags.into_iter().map(|tag| Self::Tag(tag.clone())))
+            .collect()
+    }
+}
+
+impl fmt::Display for LabelFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Project(project) => write!(f, "+{project}"),
+            Self::Tag(tag) => write!(f, "#{tag}"),
+        }
+    }
+}
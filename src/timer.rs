@@ -1,8 +1,8 @@
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use crate::logger;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TimerState {
     Work,
     WorkPaused,
@@ -13,6 +13,19 @@ pub enum TimerState {
     NotStarted,
 }
 
+impl TimerState {
+    /// Short uppercase label used by compact renderings (e.g. "12:34 WORK").
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Work | Self::WorkPaused => "WORK",
+            Self::ShortBreak | Self::ShortBreakPaused => "BREAK",
+            Self::LongBreak | Self::LongBreakPaused => "LONG BREAK",
+            Self::NotStarted => "READY",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PomodoroTimer {
     work_duration: Duration,
     break_duration: Duration,
@@ -21,6 +34,10 @@ pub struct PomodoroTimer {
     time_remaining: Duration,
     work_sessions_completed: u32,
     total_duration: Duration,
+    /// Work sessions before each long break.
+    long_break_interval: u32,
+    auto_start_breaks: bool,
+    auto_start_work: bool,
 }
 
 impl PomodoroTimer {
@@ -37,9 +54,36 @@ impl PomodoroTimer {
             time_remaining: Duration::ZERO,
             work_sessions_completed: 0,
             total_duration: Duration::ZERO,
+            long_break_interval: 4,
+            auto_start_breaks: true,
+            auto_start_work: true,
         }
     }
 
+    /// Change the session lengths, from the next session on.
+    pub fn set_durations(
+        &mut self,
+        work_minutes: u32,
+        break_minutes: u32,
+        long_break_minutes: u32,
+    ) {
+        self.work_duration = Duration::from_secs(u64::from(work_minutes) * 60);
+        self.break_duration = Duration::from_secs(u64::from(break_minutes) * 60);
+        self.long_break_duration = Duration::from_secs(u64::from(long_break_minutes) * 60);
+    }
+
+    /// Take a long break after every `interval` work sessions.
+    pub fn set_long_break_interval(&mut self, interval: u32) {
+        self.long_break_interval = interval.max(1);
+    }
+
+    /// Choose whether breaks and work sessions start on their own when the
+    /// previous session ends, or wait paused for the user.
+    pub fn set_auto_start(&mut self, breaks: bool, work: bool) {
+        self.auto_start_breaks = breaks;
+        self.auto_start_work = work;
+    }
+
     pub fn tick(&mut self, elapsed: Duration) -> bool {
         // Don't tick if paused or not started
         if self.is_paused() || self.current_state == TimerState::NotStarted {
@@ -48,6 +92,14 @@ impl PomodoroTimer {
 
         if elapsed >= self.time_remaining {
             self.complete_session();
+            let auto_start = if self.current_state == TimerState::Work {
+                self.auto_start_work
+            } else {
+                self.auto_start_breaks
+            };
+            if !auto_start {
+                self.pause();
+            }
             true
         } else {
             self.time_remaining -= elapsed;
@@ -62,7 +114,7 @@ impl PomodoroTimer {
             }
             TimerState::Work | TimerState::WorkPaused => {
                 self.work_sessions_completed += 1;
-                if self.work_sessions_completed % 4 == 0 {
+                if self.work_sessions_completed % self.long_break_interval == 0 {
                     self.start_long_break();
                 } else {
                     self.start_short_break();
@@ -81,24 +133,18 @@ impl PomodoroTimer {
         self.current_state = TimerState::Work;
         self.time_remaining = self.work_duration;
         self.total_duration = self.work_duration;
-        #[allow(clippy::cast_possible_truncation)]
-        logger::log_session_start("work", self.work_duration.as_secs() as u32 / 60);
     }
 
     fn start_short_break(&mut self) {
         self.current_state = TimerState::ShortBreak;
         self.time_remaining = self.break_duration;
         self.total_duration = self.break_duration;
-        #[allow(clippy::cast_possible_truncation)]
-        logger::log_session_start("short break", self.break_duration.as_secs() as u32 / 60);
     }
 
     fn start_long_break(&mut self) {
         self.current_state = TimerState::LongBreak;
         self.time_remaining = self.long_break_duration;
         self.total_duration = self.long_break_duration;
-        #[allow(clippy::cast_possible_truncation)]
-        logger::log_session_start("long break", self.long_break_duration.as_secs() as u32 / 60);
     }
 
     pub fn start(&mut self) {
@@ -109,6 +155,41 @@ impl PomodoroTimer {
         }
     }
 
+    /// Lengthen the current session by `delta_secs`, or shorten it when
+    /// negative, without changing the time it has already run. A session
+    /// shortened past its end completes on the next tick.
+    ///
+    /// Returns the change actually made, which is zero when no session runs.
+    pub fn adjust(&mut self, delta_secs: i64) -> i64 {
+        if self.current_state == TimerState::NotStarted {
+            return 0;
+        }
+        let delta = Duration::from_secs(delta_secs.unsigned_abs());
+        if delta_secs >= 0 {
+            self.time_remaining += delta;
+            self.total_duration += delta;
+            delta_secs
+        } else {
+            let delta = delta.min(self.time_remaining);
+            self.time_remaining -= delta;
+            self.total_duration -= delta;
+            let secs = delta.as_secs() + u64::from(delta.subsec_millis() >= 500);
+            -i64::try_from(secs).unwrap_or(i64::MAX)
+        }
+    }
+
+    /// Start the current session over from its full length, paused.
+    pub fn restart_session(&mut self) {
+        self.time_remaining = match self.current_state {
+            TimerState::NotStarted => return,
+            TimerState::Work | TimerState::WorkPaused => self.work_duration,
+            TimerState::ShortBreak | TimerState::ShortBreakPaused => self.break_duration,
+            TimerState::LongBreak | TimerState::LongBreakPaused => self.long_break_duration,
+        };
+        self.total_duration = self.time_remaining;
+        self.pause();
+    }
+
     pub fn reset(&mut self) {
         self.current_state = TimerState::NotStarted;
         self.time_remaining = Duration::ZERO;
@@ -128,6 +209,11 @@ impl PomodoroTimer {
         self.time_remaining
     }
 
+    /// Full length of the current session.
+    pub const fn total_duration(&self) -> Duration {
+        self.total_duration
+    }
+
     pub fn pause(&mut self) {
         self.current_state = match self.current_state {
             TimerState::Work => TimerState::WorkPaused,
@@ -156,12 +242,4 @@ impl PomodoroTimer {
     pub const fn work_duration(&self) -> Duration {
         self.work_duration
     }
-
-    pub const fn break_duration(&self) -> Duration {
-        self.break_duration
-    }
-
-    pub const fn long_break_duration(&self) -> Duration {
-        self.long_break_duration
-    }
 }
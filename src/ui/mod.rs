@@ -3,32 +3,226 @@
 //! This module contains all the UI rendering logic organized into dedicated submodules
 //! for better maintainability and separation of concerns.
 
-use ratatui::prelude::*;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Tabs},
+};
 
 use crate::app::App;
+use crate::config::Theme;
 use crate::data::TimerData;
+use crate::tags::Label;
 
 pub mod modal;
 pub mod screens;
+pub mod text_input;
 
-pub use screens::Screen;
+pub use screens::{Screen, ScreenId};
 
 /// Main render function that orchestrates the rendering of all UI components.
 pub fn render(frame: &mut Frame, app: &App) {
-    // Create timer data from app state
-    let timer_data = TimerData {
-        remaining_time: app.time_remaining(),
-        total_time: match app.current_state() {
-            crate::timer::TimerState::Work | crate::timer::TimerState::WorkPaused => {
-                app.timer.work_duration()
-            }
-            crate::timer::TimerState::ShortBreak | crate::timer::TimerState::ShortBreakPaused => {
-                app.timer.break_duration()
+    let timer_data = timer_data(app);
+
+    // Render the current screen, or the compact view if it does not fit
+    let area = frame.area();
+    let screen_area = screen_area(app, area);
+    if screen_area != area {
+        render_tab_bar(frame, app, Rect { height: 1, ..area });
+    }
+    let min_size = app.current_screen().min_size();
+    if screen_area.width < min_size.width || screen_area.height < min_size.height {
+        render_compact(frame, &timer_data, screen_area);
+    } else {
+        app.current_screen().render(frame, &timer_data, screen_area);
+    }
+
+    if let Some(toast) = &app.toast {
+        render_toast(frame, &toast.message, area);
+    }
+
+    // Render the naming or reflection modal on top
+    if app.naming_mode {
+        modal::render(frame, app);
+    } else if let Some(palette) = &app.palette {
+        modal::render_palette(frame, palette);
+    } else if let Some(reflection) = &app.reflection {
+        modal::render_reflection(frame, reflection);
+    } else if let Some(action) = app.confirm {
+        modal::render_confirm(frame, area, action.question());
+    }
+
+    apply_theme(frame.buffer_mut(), app.config.ui.theme);
+}
+
+/// Recolour the rendered frame for the chosen theme.
+///
+/// Screens are drawn with the default colours, which are then replaced cell
+/// by cell, so new widgets get every theme without any extra work.
+fn apply_theme(buffer: &mut Buffer, theme: Theme) {
+    match theme {
+        Theme::Default => {}
+        Theme::Monochrome => {
+            for cell in &mut buffer.content {
+                // Heatmap levels become shades instead of colours
+                if let Some(glyph) = (cell.symbol() == "■")
+                    .then(|| screens::stats::level_glyph(cell.fg))
+                    .flatten()
+                {
+                    cell.set_symbol(glyph);
+                }
+                // Keep highlights and chips visible without colour, but not
+                // the plain black behind modals
+                if !matches!(cell.bg, Color::Reset | Color::Black) {
+                    cell.modifier.insert(Modifier::REVERSED);
+                }
+                cell.fg = Color::Reset;
+                cell.bg = Color::Reset;
             }
-            crate::timer::TimerState::LongBreak | crate::timer::TimerState::LongBreakPaused => {
-                app.timer.long_break_duration()
+        }
+        Theme::HighContrast => {
+            for cell in &mut buffer.content {
+                cell.fg = bright(cell.fg);
+                if cell.bg == Color::DarkGray {
+                    cell.bg = Color::Blue;
+                }
             }
+        }
+    }
+}
+
+/// The bright variant of a colour, for the high contrast theme.
+const fn bright(color: Color) -> Color {
+    match color {
+        Color::Red => Color::LightRed,
+        Color::Green => Color::LightGreen,
+        Color::Yellow => Color::LightYellow,
+        Color::Blue => Color::LightBlue,
+        Color::Magenta => Color::LightMagenta,
+        Color::Cyan => Color::LightCyan,
+        Color::Gray | Color::DarkGray => Color::White,
+        other => other,
+    }
+}
+
+/// The part of the terminal `area` the current screen is drawn in, below the
+/// tab bar when there is one.
+pub fn screen_area(app: &App, area: Rect) -> Rect {
+    if show_tab_bar(app, area) {
+        Rect {
+            y: area.y + 1,
+            height: area.height - 1,
+            ..area
+        }
+    } else {
+        area
+    }
+}
+
+/// Whether there is room for the tab bar above the screen. It is left out of
+/// the inline and distraction-free views.
+fn show_tab_bar(app: &App, area: Rect) -> bool {
+    !app.inline
+        && app.current_screen().id() != ScreenId::Fullscreen
+        && area.width >= TAB_BAR_MIN_WIDTH
+        && area.height >= 20
+}
+
+/// Narrowest terminal the tab bar is shown in.
+const TAB_BAR_MIN_WIDTH: u16 = 72;
+
+/// A screen's title in the tab bar, after the key that opens it.
+fn tab_title(id: ScreenId) -> Line<'static> {
+    let mut spans = Vec::new();
+    if let Some(key) = id.key() {
+        spans.push(Span::styled(
+            key.to_ascii_uppercase().to_string(),
+            Style::default().fg(Color::Yellow),
+        ));
+        spans.push(Span::raw(" "));
+    }
+    spans.push(Span::raw(id.title()));
+    Line::from(spans)
+}
+
+/// The screen whose tab is at `column` of the tab bar starting at `x`.
+pub fn tab_at(x: u16, column: u16) -> Option<ScreenId> {
+    let mut start = x;
+    for id in ScreenId::ALL {
+        // A space on each side of the title, then the divider
+        let width = u16::try_from(tab_title(id).width()).unwrap_or(u16::MAX) + 2;
+        if (start..start + width).contains(&column) {
+            return Some(id);
+        }
+        start += width + 1;
+    }
+    None
+}
+
+/// Render the list of screens with their keys, the current one highlighted.
+fn render_tab_bar(frame: &mut Frame, app: &App, area: Rect) {
+    let current = app.current_screen().id();
+    let titles = ScreenId::ALL.map(tab_title);
+    let selected = ScreenId::ALL.iter().position(|id| *id == current);
+    let tabs = Tabs::new(titles)
+        .select(selected.unwrap_or(0))
+        .style(Style::default().fg(Color::Gray))
+        .highlight_style(
+            Style::default()
+                .fg(Color::White)
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .divider("│");
+    frame.render_widget(tabs, area);
+}
+
+/// A focus rating as five stars, e.g. "★★★☆☆", or empty stars when unrated.
+pub fn rating_stars(rating: Option<u8>) -> String {
+    let filled = usize::from(rating.unwrap_or(0).min(5));
+    format!("{}{}", "★".repeat(filled), "☆".repeat(5 - filled))
+}
+
+/// Colours of project and tag chips.
+const CHIP_COLORS: [Color; 6] = [
+    Color::Blue,
+    Color::Magenta,
+    Color::Green,
+    Color::Yellow,
+    Color::Cyan,
+    Color::LightRed,
+];
+
+/// Coloured chips for a project and tags, each keeping its colour across runs.
+pub fn label_chips(project: Option<&str>, tags: &[String]) -> Vec<Span<'static>> {
+    let chips = project
+        .map(|project| format!("+{project}"))
+        .into_iter()
+        .chain(tags.iter().map(|tag| format!("#{tag}")));
+    let mut spans = Vec::new();
+    for chip in chips {
+        let hash = chip.bytes().fold(0usize, |hash, byte| {
+            hash.wrapping_mul(31).wrapping_add(usize::from(byte))
+        });
+        let color = CHIP_COLORS[hash % CHIP_COLORS.len()];
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(
+            format!(" {chip} "),
+            Style::default().fg(Color::Black).bg(color),
+        ));
+    }
+    spans
+}
+
+/// Create timer data from app state.
+pub fn timer_data(app: &App) -> TimerData {
+    let label = Label::parse(&app.current_session_name);
+    TimerData {
+        state: app.current_state(),
+        remaining_time: app.time_remaining(),
+        // The session on the timer keeps its length when the settings change
+        total_time: match app.current_state() {
             crate::timer::TimerState::NotStarted => app.timer.work_duration(),
+            _ => app.timer.total_duration(),
         },
         is_running: app.current_state() != crate::timer::TimerState::NotStarted
             && app.current_state() != crate::timer::TimerState::WorkPaused
@@ -44,15 +238,67 @@ pub fn render(frame: &mut Frame, app: &App) {
         session_start_time: app
             .current_session_start
             .map(|dt| dt.format("%H:%M:%S").to_string()),
-        session_name: app.current_session_name.clone(),
+        session_name: label.name,
+        project: label.project,
+        tags: label.tags,
         naming_mode: app.naming_mode,
-    };
+        goals: app.goal_progress,
+        streaks: app.streaks,
+    }
+}
 
-    // Render the current screen
-    app.current_screen.render(frame, &timer_data, frame.area());
+/// Render the single-line "12:34 WORK" fallback used in very small terminals.
+///
+/// A "terminal too small" notice is added underneath when there is room for it.
+pub fn render_compact(frame: &mut Frame, timer_data: &TimerData, area: Rect) {
+    if area.is_empty() {
+        return;
+    }
 
-    // Render naming modal on top if in naming mode
-    if app.naming_mode {
-        modal::render(frame, app);
+    let line_y = area.y + area.height.saturating_sub(1) / 2;
+    let timer_line = Paragraph::new(timer_data.compact_line())
+        .style(Style::default().add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center);
+    frame.render_widget(timer_line, Rect::new(area.x, line_y, area.width, 1));
+
+    if area.height >= 3 {
+        let notice = Paragraph::new("Terminal too small")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        frame.render_widget(notice, Rect::new(area.x, line_y + 1, area.width, 1));
+    }
+}
+
+/// Render a toast message at the top of the screen, boxed when there is room.
+fn render_toast(frame: &mut Frame, message: &str, area: Rect) {
+    if area.is_empty() {
+        return;
+    }
+
+    let text_width = u16::try_from(Line::from(message).width()).unwrap_or(u16::MAX);
+    let boxed = area.height >= 6 && area.width >= 12;
+    let (width, height) = if boxed {
+        (text_width.saturating_add(4).min(area.width), 3)
+    } else {
+        (area.width, 1)
+    };
+    let toast_area = Rect::new(area.x + (area.width - width) / 2, area.y, width, height);
+
+    let mut toast = Paragraph::new(message)
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center);
+    if boxed {
+        toast = toast.block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
     }
+    frame.render_widget(Clear, toast_area);
+    frame.render_widget(toast, toast_area);
 }
@@ -2,24 +2,52 @@
 
 use ratatui::{
     prelude::*,
-    widgets::{Block, BorderType, Borders, Paragraph},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
 };
 
-use crate::app::App;
+use super::text_input::TextInput;
+use crate::app::{App, Reflection, ReflectionField};
+use crate::completion::Suggestion;
+use crate::palette::Palette;
+
+/// Height of the full naming modal, borders included.
+const MODAL_HEIGHT: u16 = 9;
+
+/// Height of the full reflection modal, borders included.
+const REFLECTION_HEIGHT: u16 = 12;
+
+/// Most actions listed at once in the command palette.
+const PALETTE_MAX_ACTIONS: u16 = 12;
+
+/// Narrowest terminal the full naming modal is drawn in.
+const MODAL_MIN_WIDTH: u16 = 24;
 
 /// Renders the naming modal overlay for session naming functionality.
 pub fn render(frame: &mut Frame, app: &App) {
     let area = frame.area();
 
+    // Fall back to a single input line when the modal cannot fit
+    if area.height < MODAL_HEIGHT || area.width < MODAL_MIN_WIDTH {
+        render_inline_prompt(frame, app, area);
+        return;
+    }
+    let suggestions = &app.completions.suggestions;
+    let shown = suggestions
+        .len()
+        .min(usize::from(area.height - MODAL_HEIGHT));
+
     // Create a larger centered modal area
-    let modal_width = (area.width * 7 / 10).min(70);
-    let modal_height = 9; // Increased height
-    let modal_x = (area.width - modal_width) / 2;
-    let modal_y = (area.height - modal_height) / 2;
+    let modal_width = (area.width * 7 / 10)
+        .clamp(MODAL_MIN_WIDTH, 70)
+        .min(area.width);
+    let modal_height = MODAL_HEIGHT + u16::try_from(shown).unwrap_or_default();
+    let modal_x = area.x + (area.width - modal_width) / 2;
+    let modal_y = area.y + (area.height - modal_height) / 2;
 
     let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
 
     // Clear the background
+    frame.render_widget(Clear, modal_area);
     let clear_block = Block::default()
         .style(Style::default().bg(Color::Black))
         .borders(Borders::ALL)
@@ -34,6 +62,7 @@ pub fn render(frame: &mut Frame, app: &App) {
         .constraints([
             Constraint::Length(2), // Title - more space
             Constraint::Length(3), // Input - enough space for content + borders
+            Constraint::Length(u16::try_from(shown).unwrap_or_default()), // Suggestions
             Constraint::Length(1), // Instructions
         ])
         .split(modal_area);
@@ -50,29 +79,344 @@ pub fn render(frame: &mut Frame, app: &App) {
     frame.render_widget(title, chunks[0]);
 
     // Input field with proper height
-    let input_display = if app.naming_input.is_empty() {
-        " |".to_string() // Show cursor when empty with padding
+    render_input(
+        frame,
+        chunks[1],
+        &app.naming_input,
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .border_type(BorderType::Rounded)
+            .title("Session Name"),
+        true,
+    );
+
+    // Previously used names
+    let lines: Vec<Line> = suggestions
+        .iter()
+        .take(shown)
+        .enumerate()
+        .map(|(index, suggestion)| {
+            suggestion_line(suggestion, app.completions.selected == Some(index))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[2]);
+
+    // Instructions
+    let instructions = Paragraph::new(if suggestions.is_empty() {
+        "Enter to save • Esc to cancel"
     } else {
-        format!(" {}|", app.naming_input) // Show content + cursor with padding
+        "Tab complete • Enter save • Esc cancel"
+    })
+    .style(Style::default().fg(Color::Gray))
+    .alignment(ratatui::layout::Alignment::Center);
+
+    frame.render_widget(instructions, chunks[3]);
+}
+
+/// Renders the command palette near the top of the screen: the search input
+/// and the matching actions with their keys.
+pub fn render_palette(frame: &mut Frame, palette: &Palette) {
+    let area = frame.area();
+    if area.height < 5 || area.width < MODAL_MIN_WIDTH {
+        render_inline_input(frame, area, ": ", &palette.input);
+        return;
+    }
+
+    let width = (area.width * 7 / 10)
+        .clamp(MODAL_MIN_WIDTH, 70)
+        .min(area.width);
+    // Borders, input, separator line and at least one action
+    let shown = palette
+        .matches
+        .len()
+        .clamp(1, usize::from((area.height - 4).min(PALETTE_MAX_ACTIONS)));
+    let height = 4 + u16::try_from(shown).unwrap_or_default();
+    let palette_area = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 4,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, palette_area);
+    let block = Block::default()
+        .style(Style::default().bg(Color::Black))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .border_type(BorderType::Rounded)
+        .title("Commands");
+    let inner = block.inner(palette_area);
+    frame.render_widget(block, palette_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Input
+            Constraint::Length(1), // Separator
+            Constraint::Min(0),    // Actions
+        ])
+        .split(inner);
+    frame.render_widget(
+        Paragraph::new(":").style(Style::default().fg(Color::Yellow)),
+        chunks[0],
+    );
+    palette.input.render(
+        frame,
+        Rect {
+            x: chunks[0].x + 2,
+            width: chunks[0].width.saturating_sub(2),
+            ..chunks[0]
+        },
+        Style::default().fg(Color::White),
+        true,
+    );
+    frame.render_widget(
+        Paragraph::new("─".repeat(usize::from(chunks[1].width)))
+            .style(Style::default().fg(Color::DarkGray)),
+        chunks[1],
+    );
+
+    if palette.matches.is_empty() {
+        frame.render_widget(
+            Paragraph::new("  No matching command").style(Style::default().fg(Color::Gray)),
+            chunks[2],
+        );
+        return;
+    }
+
+    // Scroll so the selected action stays visible
+    let first = palette.selected.saturating_sub(shown - 1);
+    let lines: Vec<Line> = palette
+        .matches
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(shown)
+        .map(|(index, found)| {
+            let mut line = suggestion_line(&found.title, index == palette.selected);
+            if let Some(key) = found.action.key_hint() {
+                let used = line.width() + key.len() + 1;
+                let padding = usize::from(chunks[2].width).saturating_sub(used);
+                line.spans.push(Span::raw(" ".repeat(padding)));
+                line.spans
+                    .push(Span::styled(key, Style::default().fg(Color::DarkGray)));
+            }
+            line
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[2]);
+}
+
+/// A suggested name, with the characters matching the input highlighted.
+fn suggestion_line(suggestion: &Suggestion, selected: bool) -> Line<'_> {
+    let (marker, base) = if selected {
+        (
+            "› ",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        ("  ", Style::default().fg(Color::Gray))
+    };
+    let matched = base.fg(if selected {
+        Color::Black
+    } else {
+        Color::Yellow
+    });
+    let matched = matched.add_modifier(Modifier::UNDERLINED);
+
+    let mut spans = vec![Span::styled(marker, base)];
+    spans.extend(suggestion.name.char_indices().map(|(offset, c)| {
+        let style = if suggestion.matched.contains(&offset) {
+            matched
+        } else {
+            base
+        };
+        Span::styled(c.to_string(), style)
+    }));
+    Line::from(spans)
+}
+
+/// Renders the naming input as a single line at the bottom of the terminal.
+fn render_inline_prompt(frame: &mut Frame, app: &App, area: Rect) {
+    if area.is_empty() {
+        return;
+    }
+
+    // Show the name browsed to with Up/Down in place of the typed text
+    if let Some(name) = app.completions.selection() {
+        let prompt_area = Rect::new(area.x, area.bottom() - 1, area.width, 1);
+        let prompt = Paragraph::new(format!("Name: {name}"))
+            .style(Style::default().fg(Color::Black).bg(Color::Cyan));
+        frame.render_widget(Clear, prompt_area);
+        frame.render_widget(prompt, prompt_area);
+        return;
+    }
+
+    render_inline_input(frame, area, "Name: ", &app.naming_input);
+}
+
+/// Style of the text inside input fields.
+fn input_style() -> Style {
+    Style::default().fg(Color::Black).bg(Color::White)
+}
+
+/// Renders an input field inside `block`, with one column of padding.
+pub fn render_input(frame: &mut Frame, area: Rect, input: &TextInput, block: Block, focused: bool) {
+    let inner = block.inner(area);
+    frame.render_widget(block.style(input_style()), area);
+    let padded = Rect {
+        x: inner.x.saturating_add(1).min(inner.right()),
+        width: inner.width.saturating_sub(1),
+        ..inner
     };
+    input.render(frame, padded, input_style(), focused);
+}
+
+/// Renders a label and an input as a single line at the bottom of `area`.
+fn render_inline_input(frame: &mut Frame, area: Rect, label: &str, input: &TextInput) {
+    let prompt_area = Rect::new(area.x, area.bottom() - 1, area.width, 1);
+    frame.render_widget(Clear, prompt_area);
+    frame.render_widget(Paragraph::new(label).style(input_style()), prompt_area);
+
+    let label_width = u16::try_from(Line::from(label).width()).unwrap_or(u16::MAX);
+    let input_area = Rect {
+        x: prompt_area
+            .x
+            .saturating_add(label_width)
+            .min(prompt_area.right()),
+        width: prompt_area.width.saturating_sub(label_width),
+        ..prompt_area
+    };
+    input.render(frame, input_area, input_style(), true);
+}
 
-    let input = Paragraph::new(input_display)
-        .style(Style::default().fg(Color::Black).bg(Color::White))
-        .alignment(ratatui::layout::Alignment::Left)
+/// Renders a yes/no question in a box in the middle of `area`.
+pub fn render_confirm(frame: &mut Frame, area: Rect, question: &str) {
+    let width = u16::try_from(Line::from(question).width() + 4)
+        .unwrap_or(u16::MAX)
+        .min(area.width);
+    let height = 3.min(area.height);
+    let confirm_area = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    let confirm = Paragraph::new(question)
+        .style(Style::default().fg(Color::Yellow))
+        .alignment(Alignment::Center)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
                 .border_type(BorderType::Rounded)
-                .title("Session Name"),
+                .border_style(Style::default().fg(Color::Red)),
         );
+    frame.render_widget(Clear, confirm_area);
+    frame.render_widget(confirm, confirm_area);
+}
 
-    frame.render_widget(input, chunks[1]);
+/// Renders the reflection modal asking what got done and how focused it was.
+pub fn render_reflection(frame: &mut Frame, reflection: &Reflection) {
+    let area = frame.area();
 
-    // Instructions
-    let instructions = Paragraph::new("Enter to save • Esc to cancel")
+    if area.height < REFLECTION_HEIGHT || area.width < MODAL_MIN_WIDTH {
+        render_inline_reflection(frame, reflection, area);
+        return;
+    }
+
+    let modal_width = (area.width * 7 / 10)
+        .clamp(MODAL_MIN_WIDTH, 70)
+        .min(area.width);
+    let modal_area = Rect::new(
+        area.x + (area.width - modal_width) / 2,
+        area.y + (area.height - REFLECTION_HEIGHT) / 2,
+        modal_width,
+        REFLECTION_HEIGHT,
+    );
+
+    frame.render_widget(Clear, modal_area);
+    let clear_block = Block::default()
+        .style(Style::default().bg(Color::Black))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .border_type(BorderType::Rounded);
+    frame.render_widget(clear_block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2), // Title
+            Constraint::Length(3), // Note
+            Constraint::Length(3), // Rating
+            Constraint::Length(1), // Instructions
+        ])
+        .split(modal_area);
+
+    let title = Paragraph::new("🍅 Work session complete!")
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center);
+    frame.render_widget(title, chunks[0]);
+
+    let note_focused = reflection.focus == ReflectionField::Note;
+    render_input(
+        frame,
+        chunks[1],
+        &reflection.note,
+        field_block("What did you get done?", note_focused),
+        note_focused,
+    );
+
+    let rating_focused = reflection.focus == ReflectionField::Rating;
+    let rating = Paragraph::new(format!(" {}", super::rating_stars(reflection.rating)))
+        .style(Style::default().fg(Color::Yellow))
+        .block(field_block("Focus rating (1-5)", rating_focused));
+    frame.render_widget(rating, chunks[2]);
+
+    let instructions = Paragraph::new("Tab to switch • Enter to save • Esc to skip")
         .style(Style::default().fg(Color::Gray))
-        .alignment(ratatui::layout::Alignment::Center);
+        .alignment(Alignment::Center);
+    frame.render_widget(instructions, chunks[3]);
+}
+
+/// Bordered block of a modal input, highlighted when it has the focus.
+pub fn field_block(title: &str, focused: bool) -> Block<'_> {
+    let color = if focused {
+        Color::Cyan
+    } else {
+        Color::DarkGray
+    };
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .border_type(BorderType::Rounded)
+        .title(title)
+}
+
+/// Renders the reflection as a single line at the bottom of the terminal.
+fn render_inline_reflection(frame: &mut Frame, reflection: &Reflection, area: Rect) {
+    if area.is_empty() {
+        return;
+    }
+
+    if reflection.focus == ReflectionField::Note {
+        render_inline_input(frame, area, "Done: ", &reflection.note);
+        return;
+    }
 
-    frame.render_widget(instructions, chunks[2]);
+    let prompt_area = Rect::new(area.x, area.bottom() - 1, area.width, 1);
+    let prompt = Paragraph::new(format!(
+        "Focus 1-5: {}",
+        super::rating_stars(reflection.rating)
+    ))
+    .style(input_style());
+    frame.render_widget(Clear, prompt_area);
+    frame.render_widget(prompt, prompt_area);
 }
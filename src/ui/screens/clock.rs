@@ -4,20 +4,23 @@
 
 use chrono::Local;
 use ratatui::{
-    layout::Rect,
+    layout::{Rect, Size},
     style::{Color, Modifier, Style},
     widgets::{Block, BorderType, Borders, Paragraph},
     Frame,
 };
-use std::any::Any;
 
-use super::Screen;
+use super::{Screen, ScreenId};
 use crate::data::TimerData;
 
 /// Clock screen that displays the current time.
 pub struct ClockScreen;
 
 impl Screen for ClockScreen {
+    fn id(&self) -> ScreenId {
+        ScreenId::Clock
+    }
+
     fn render(&self, frame: &mut Frame, _timer_data: &TimerData, area: Rect) {
         let now = Local::now();
         let time_str = now.format("%H:%M").to_string();
@@ -28,18 +31,26 @@ impl Screen for ClockScreen {
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD),
             )
-            .alignment(ratatui::layout::Alignment::Center)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .title("Current Time"),
-            );
+            .alignment(ratatui::layout::Alignment::Center);
+
+        // Without room for the borders, show the bare time on the middle line
+        if area.height < 3 {
+            let line_y = area.y + area.height.saturating_sub(1) / 2;
+            frame.render_widget(clock_display, Rect::new(area.x, line_y, area.width, 1));
+            return;
+        }
+
+        let clock_display = clock_display.block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title("Current Time"),
+        );
 
         frame.render_widget(clock_display, area);
     }
 
-    fn as_any(&self) -> &dyn Any {
-        self
+    fn min_size(&self) -> Size {
+        Size::new(5, 1)
     }
 }
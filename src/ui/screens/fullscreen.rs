@@ -3,23 +3,34 @@
 //! This screen displays a minimal, focused timer view that maximizes
 //! the timer display for distraction-free work sessions.
 
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Rect, Size},
     style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use std::any::Any;
 
-use super::Screen;
+use super::{Screen, ScreenId};
+use crate::action::Action;
 use crate::data::TimerData;
 
 /// Fullscreen screen that displays a minimal, focused timer view.
 pub struct FullscreenScreen;
 
 impl Screen for FullscreenScreen {
+    fn id(&self) -> ScreenId {
+        ScreenId::Fullscreen
+    }
+
     fn render(&self, frame: &mut Frame, timer_data: &TimerData, area: Rect) {
+        // Drop the help line when there is barely room for the timer
+        if area.height < 5 {
+            Self::render_timer(frame, timer_data, area);
+            return;
+        }
+
         // Create the main layout
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -33,8 +44,18 @@ impl Screen for FullscreenScreen {
         Self::render_help(frame, chunks[1]);
     }
 
-    fn as_any(&self) -> &dyn Any {
-        self
+    fn min_size(&self) -> Size {
+        Size::new(5, 1)
+    }
+
+    fn handle_mouse(
+        &mut self,
+        mouse: MouseEvent,
+        _timer_data: &TimerData,
+        _area: Rect,
+    ) -> Option<Action> {
+        // The whole screen is the timer
+        (mouse.kind == MouseEventKind::Down(MouseButton::Left)).then_some(Action::StartPause)
     }
 }
 
@@ -54,15 +75,11 @@ impl FullscreenScreen {
                 / 2;
         let timer_y = area.y + (area.height.saturating_sub(1)) / 2;
 
+        let timer_width = u16::try_from(timer_data.format_time().len()).unwrap_or(0);
         let timer = Paragraph::new(timer_text).block(Block::default().borders(Borders::NONE));
         frame.render_widget(
             timer,
-            Rect::new(
-                timer_x,
-                timer_y,
-                u16::try_from(timer_data.format_time().len()).unwrap_or(0),
-                1,
-            ),
+            Rect::new(timer_x, timer_y, timer_width, 1).intersection(area),
         );
     }
 
@@ -3,22 +3,40 @@
 //! This screen displays helpful information about using the Pomodoro timer,
 //! including keyboard shortcuts, session types, and general instructions.
 
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent, MouseEventKind};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Rect, Size},
     style::{Color, Modifier, Style},
     widgets::{Block, BorderType, Borders, Paragraph},
     Frame,
 };
-use std::any::Any;
 
-use super::Screen;
+use super::{Screen, ScreenId, ScreenKey};
+use crate::action::Action;
 use crate::data::TimerData;
 
+/// Keyboard shortcuts, one per line.
+const SHORTCUTS: &str = "• [Space/Enter] Start/Pause timer\n• [R] Reset timer to beginning\n• [S] Skip current session\n• [V] Void current session (start it over, unrecorded)\n• [U] Undo the last reset, skip or void\n• [+/-] Extend/shorten the session by a minute (] and [ by five)\n• [N] Name current session (+project #tag)\n• [F] Toggle fullscreen mode\n• [H] Show this help screen\n• [C] Show the clock\n• [T] Statistics heatmap (arrows to move, PgUp/PgDn by month, M to switch metric, P to filter by project or tag)\n• [L] Session history (/ to filter, E to edit, D to delete, A to add)\n• [O] Settings (durations, auto-start, theme, notifications)\n• [Esc] Back to the previous screen, [Tab] next screen\n• [:] or [Ctrl+P] Command palette: find any action by name\n• [Ctrl+Z] Suspend to the shell (fg to come back)\n• [Q] Quit application\n• [Ctrl+C] Force quit\n• Mouse: click a tab, the timer, the session or a control; scroll lists";
+
 /// Help screen that displays usage instructions and keyboard shortcuts.
-pub struct HelpScreen;
+#[derive(Default)]
+pub struct HelpScreen {
+    /// Lines the keyboard shortcuts are scrolled by.
+    scroll: u16,
+}
 
 impl Screen for HelpScreen {
+    fn id(&self) -> ScreenId {
+        ScreenId::Help
+    }
+
     fn render(&self, frame: &mut Frame, _timer_data: &TimerData, area: Rect) {
+        // Header and footer are the first things to go in short terminals
+        if area.height < 16 {
+            self.render_content(frame, area);
+            return;
+        }
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -29,16 +47,45 @@ impl Screen for HelpScreen {
             .split(area);
 
         Self::render_header(frame, chunks[0]);
-        Self::render_content(frame, chunks[1]);
+        self.render_content(frame, chunks[1]);
         Self::render_footer(frame, chunks[2]);
     }
 
-    fn as_any(&self) -> &dyn Any {
-        self
+    fn min_size(&self) -> Size {
+        Size::new(30, 6)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ScreenKey {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.scroll_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll_by(1),
+            _ => return ScreenKey::Ignored,
+        }
+        ScreenKey::Handled
+    }
+
+    fn handle_mouse(
+        &mut self,
+        mouse: MouseEvent,
+        _timer_data: &TimerData,
+        _area: Rect,
+    ) -> Option<Action> {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.scroll_by(-1),
+            MouseEventKind::ScrollDown => self.scroll_by(1),
+            _ => {}
+        }
+        None
     }
 }
 
 impl HelpScreen {
+    /// Scroll the keyboard shortcuts, never past their last line.
+    fn scroll_by(&mut self, lines: i16) {
+        let last = u16::try_from(SHORTCUTS.lines().count().saturating_sub(1)).unwrap_or(0);
+        self.scroll = self.scroll.saturating_add_signed(lines).min(last);
+    }
+
     /// Render the header section with title.
     fn render_header(frame: &mut Frame, area: Rect) {
         let header = Paragraph::new("🍅 Tomat - Help & Instructions")
@@ -57,20 +104,26 @@ impl HelpScreen {
     }
 
     /// Render the main content with instructions and shortcuts.
-    fn render_content(frame: &mut Frame, area: Rect) {
+    fn render_content(&self, frame: &mut Frame, area: Rect) {
+        // Only keep the keyboard shortcuts when the other sections do not fit
+        if area.height < 31 {
+            self.render_shortcuts(frame, area);
+            return;
+        }
+
         let content_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(4), // What is Pomodoro
-                Constraint::Length(6), // Session types
-                Constraint::Length(8), // Keyboard shortcuts
-                Constraint::Min(0),    // Additional info
+                Constraint::Length(4),  // What is Pomodoro
+                Constraint::Length(6),  // Session types
+                Constraint::Length(21), // Keyboard shortcuts
+                Constraint::Min(0),     // Additional info
             ])
             .split(area);
 
         Self::render_pomodoro_info(frame, content_chunks[0]);
         Self::render_session_types(frame, content_chunks[1]);
-        Self::render_shortcuts(frame, content_chunks[2]);
+        self.render_shortcuts(frame, content_chunks[2]);
         Self::render_additional_info(frame, content_chunks[3]);
     }
 
@@ -90,7 +143,7 @@ impl HelpScreen {
 
     /// Render information about different session types.
     fn render_session_types(frame: &mut Frame, area: Rect) {
-        let text = "• Work Session: 25 minutes of focused work\n• Short Break: 5 minutes of rest\n• Long Break: 15 minutes after 4 work sessions\n\nCustomize durations with CLI arguments: --work, --short-break, --long-break";
+        let text = "• Work Session: 25 minutes of focused work\n• Short Break: 5 minutes of rest\n• Long Break: 15 minutes after 4 work sessions\n\nChange durations in the settings [O] or with --work, --short-break, --long-break-time";
         let paragraph = Paragraph::new(text)
             .block(
                 Block::default()
@@ -103,9 +156,9 @@ impl HelpScreen {
     }
 
     /// Render keyboard shortcuts.
-    fn render_shortcuts(frame: &mut Frame, area: Rect) {
-        let text = "• [Space/Enter] Start/Pause timer\n• [R] Reset timer to beginning\n• [S] Skip current session\n• [N] Name current session\n• [F] Toggle fullscreen mode\n• [H] Show this help screen\n• [Q] Quit application\n• [Ctrl+C] Force quit";
-        let paragraph = Paragraph::new(text)
+    fn render_shortcuts(&self, frame: &mut Frame, area: Rect) {
+        let paragraph = Paragraph::new(SHORTCUTS)
+            .scroll((self.scroll, 0))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
@@ -118,7 +171,7 @@ impl HelpScreen {
 
     /// Render additional information.
     fn render_additional_info(frame: &mut Frame, area: Rect) {
-        let text = "• Sessions are automatically logged to tomat.log\n• Progress is shown as a percentage complete\n• Use [N] to name sessions for better tracking\n• The app follows the MVC architecture pattern\n• Built with Rust and ratatui for terminal UI";
+        let text = "• Sessions are automatically recorded to the history\n• Progress is shown as a percentage complete\n• Use [N] to name sessions for better tracking\n• The app follows the MVC architecture pattern\n• Built with Rust and ratatui for terminal UI";
         let paragraph = Paragraph::new(text)
             .block(
                 Block::default()
@@ -132,7 +185,7 @@ impl HelpScreen {
 
     /// Render the footer with navigation hint.
     fn render_footer(frame: &mut Frame, area: Rect) {
-        let footer = Paragraph::new("Press [H] to return to timer | [Q] to quit")
+        let footer = Paragraph::new("[↑↓] Scroll | [Esc] Back | [Tab] Next screen | [Q] Quit")
             .style(Style::default().fg(Color::Gray))
             .alignment(ratatui::layout::Alignment::Center)
             .block(
@@ -0,0 +1,623 @@
+//! Session history screen implementation for the Pomodoro timer application.
+//!
+//! This screen lists the recorded sessions, newest first, in a scrollable
+//! table that can be filtered. Sessions can be renamed or annotated after the
+//! fact, deleted, or added by hand when the timer was not running.
+
+use chrono::{Local, NaiveDateTime, NaiveTime, TimeZone};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position, Rect, Size},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::time::Duration;
+
+use super::{Screen, ScreenId, ScreenKey, ScreenRequest};
+use crate::action::Action;
+use crate::data::TimerData;
+use crate::history::{History, SessionKind, SessionRecord};
+use crate::streaks::Streaks;
+use crate::tags::Label;
+use crate::ui::modal::{field_block, render_confirm, render_input};
+use crate::ui::text_input::TextInput;
+
+/// Format of the start time in the table and the add form.
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// A change to the history requested from the screen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryChange {
+    Update(SessionRecord),
+    Remove(u64),
+    Insert(SessionRecord),
+}
+
+/// What the keys currently act on.
+enum Mode {
+    Browse,
+    Filter,
+    /// Editing the session with the given id.
+    Edit(u64, Form),
+    Add(Form),
+    /// Waiting for the deletion of the session with the given id to be confirmed.
+    ConfirmDelete(u64),
+}
+
+/// A few text fields edited together.
+struct Form {
+    fields: Vec<(&'static str, TextInput)>,
+    focus: usize,
+    error: Option<String>,
+}
+
+impl Form {
+    const fn new(fields: Vec<(&'static str, TextInput)>) -> Self {
+        Self {
+            fields,
+            focus: 0,
+            error: None,
+        }
+    }
+
+    fn value(&self, index: usize) -> &str {
+        self.fields[index].1.value().trim()
+    }
+
+    /// Handle a key other than Enter and Esc.
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Tab | KeyCode::Down => self.focus = (self.focus + 1) % self.fields.len(),
+            KeyCode::BackTab | KeyCode::Up => {
+                self.focus = (self.focus + self.fields.len() - 1) % self.fields.len();
+            }
+            _ => {
+                self.fields[self.focus].1.handle_key(key);
+            }
+        }
+    }
+
+    /// Height of the form, borders included.
+    fn height(&self) -> u16 {
+        u16::try_from(self.fields.len() * 3 + 3).unwrap_or(u16::MAX)
+    }
+}
+
+/// Screen listing past sessions.
+pub struct HistoryScreen {
+    /// Every recorded session, newest first.
+    sessions: Vec<SessionRecord>,
+    /// Indices in `sessions` of the ones matching the filter.
+    visible: Vec<usize>,
+    /// Position of the selected session in `visible`.
+    selected: usize,
+    filter: TextInput,
+    mode: Mode,
+    /// Length given to sessions added by hand.
+    default_length: Duration,
+}
+
+impl Screen for HistoryScreen {
+    fn id(&self) -> ScreenId {
+        ScreenId::History
+    }
+
+    fn render(&self, frame: &mut Frame, _timer_data: &TimerData, area: Rect) {
+        let show_footer = area.height >= 12;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),                                  // Sessions
+                Constraint::Length(if show_footer { 3 } else { 0 }), // Footer
+            ])
+            .split(area);
+
+        self.render_table(frame, chunks[0]);
+        if show_footer {
+            self.render_footer(frame, chunks[1]);
+        }
+
+        match &self.mode {
+            Mode::Edit(_, form) => Self::render_form(frame, "Edit session", form, area),
+            Mode::Add(form) => Self::render_form(frame, "Add a work session", form, area),
+            Mode::ConfirmDelete(_) => self.render_confirm_delete(frame, area),
+            Mode::Browse | Mode::Filter => {}
+        }
+    }
+
+    fn min_size(&self) -> Size {
+        Size::new(40, 8)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ScreenKey {
+        if matches!(key.code, KeyCode::Char('c' | 'z'))
+            && key.modifiers.contains(KeyModifiers::CONTROL)
+        {
+            // Always let Ctrl+C through to quit, and Ctrl+Z to suspend
+            return ScreenKey::Ignored;
+        }
+
+        match &mut self.mode {
+            Mode::Browse => return self.handle_browse_key(key),
+            Mode::Filter => match key.code {
+                KeyCode::Enter => self.mode = Mode::Browse,
+                KeyCode::Esc => {
+                    self.filter = TextInput::default();
+                    self.apply_filter();
+                    self.mode = Mode::Browse;
+                }
+                _ => {
+                    if self.filter.handle_key(key) {
+                        self.apply_filter();
+                    }
+                }
+            },
+            Mode::ConfirmDelete(id) => {
+                let id = *id;
+                self.mode = Mode::Browse;
+                if matches!(key.code, KeyCode::Char('y' | 'Y')) {
+                    return ScreenKey::Request(ScreenRequest::EditHistory(HistoryChange::Remove(
+                        id,
+                    )));
+                }
+            }
+            Mode::Edit(_, form) | Mode::Add(form) => match key.code {
+                KeyCode::Esc => self.mode = Mode::Browse,
+                KeyCode::Enter => return self.submit_form(),
+                _ => form.handle_key(key),
+            },
+        }
+        ScreenKey::Handled
+    }
+
+    fn handle_mouse(
+        &mut self,
+        mouse: MouseEvent,
+        _timer_data: &TimerData,
+        area: Rect,
+    ) -> Option<Action> {
+        if !matches!(self.mode, Mode::Browse) || self.visible.is_empty() {
+            return None;
+        }
+        let last = self.visible.len() - 1;
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.selected = self.selected.saturating_sub(1),
+            MouseEventKind::ScrollDown => self.selected = (self.selected + 1).min(last),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let rows = self.rows_area(area);
+                if rows.contains(Position::new(mouse.column, mouse.row)) {
+                    // The table scrolls just enough to show the selected row
+                    let first = self
+                        .selected
+                        .saturating_sub(usize::from(rows.height).saturating_sub(1));
+                    let clicked = first + usize::from(mouse.row - rows.y);
+                    self.selected = clicked.min(last);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        match &mut self.mode {
+            Mode::Filter => {
+                self.filter.insert_str(text);
+                self.apply_filter();
+            }
+            Mode::Edit(_, form) | Mode::Add(form) => form.fields[form.focus].1.insert_str(text),
+            Mode::Browse | Mode::ConfirmDelete(_) => {}
+        }
+    }
+
+    fn history_changed(&mut self, history: &History, _streaks: Streaks) {
+        self.reload(history);
+    }
+}
+
+impl HistoryScreen {
+    /// Build the screen from the session history, with the newest session selected.
+    pub fn new(history: &History, default_length: Duration) -> Self {
+        let mut screen = Self {
+            sessions: Vec::new(),
+            visible: Vec::new(),
+            selected: 0,
+            filter: TextInput::default(),
+            mode: Mode::Browse,
+            default_length,
+        };
+        screen.reload(history);
+        screen
+    }
+
+    /// Refresh the sessions after the history changed, keeping the selection
+    /// on the same session when it still exists.
+    fn reload(&mut self, history: &History) {
+        let selected_id = self.selected_session().map(|record| record.id);
+        self.sessions = history.records().iter().rev().cloned().collect();
+        self.apply_filter();
+        if let Some(position) = selected_id.and_then(|id| {
+            self.visible
+                .iter()
+                .position(|index| self.sessions[*index].id == id)
+        }) {
+            self.selected = position;
+        }
+    }
+
+    fn handle_browse_key(&mut self, key: KeyEvent) -> ScreenKey {
+        let last = self.visible.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(last),
+            KeyCode::PageUp => self.selected = self.selected.saturating_sub(10),
+            KeyCode::PageDown => self.selected = (self.selected + 10).min(last),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = last,
+            KeyCode::Char('/') => self.mode = Mode::Filter,
+            KeyCode::Esc if !self.filter.value().is_empty() => {
+                self.filter = TextInput::default();
+                self.apply_filter();
+            }
+            KeyCode::Char('e') | KeyCode::Enter => {
+                if let Some(record) = self.selected_session() {
+                    let form = Form::new(vec![
+                        (
+                            "Name (+project #tag)",
+                            TextInput::with_value(&record.label()),
+                        ),
+                        ("Note", TextInput::with_value(&record.note)),
+                    ]);
+                    self.mode = Mode::Edit(record.id, form);
+                }
+            }
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some(record) = self.selected_session() {
+                    self.mode = Mode::ConfirmDelete(record.id);
+                }
+            }
+            KeyCode::Char('a') => {
+                let start = Local::now() - self.default_length;
+                let minutes = self.default_length.as_secs() / 60;
+                self.mode = Mode::Add(Form::new(vec![
+                    (
+                        "Start (YYYY-MM-DD HH:MM)",
+                        TextInput::with_value(&start.format(TIME_FORMAT).to_string()),
+                    ),
+                    ("Minutes", TextInput::with_value(&minutes.to_string())),
+                    ("Name (+project #tag)", TextInput::default()),
+                ]));
+            }
+            _ => return ScreenKey::Ignored,
+        }
+        ScreenKey::Handled
+    }
+
+    /// Turn the open form into a change, or show what is wrong with it.
+    fn submit_form(&mut self) -> ScreenKey {
+        let result = match &self.mode {
+            Mode::Edit(id, form) => self.edited_session(*id, form),
+            Mode::Add(form) => self.added_session(form),
+            _ => return ScreenKey::Handled,
+        };
+        match result {
+            Ok(change) => {
+                self.mode = Mode::Browse;
+                ScreenKey::Request(ScreenRequest::EditHistory(change))
+            }
+            Err(e) => {
+                if let Mode::Edit(_, form) | Mode::Add(form) = &mut self.mode {
+                    form.error = Some(e);
+                }
+                ScreenKey::Handled
+            }
+        }
+    }
+
+    fn edited_session(&self, id: u64, form: &Form) -> Result<HistoryChange, String> {
+        let mut record = self
+            .sessions
+            .iter()
+            .find(|record| record.id == id)
+            .cloned()
+            .ok_or("the session no longer exists")?;
+        let label = Label::parse(form.value(0));
+        record.name = label.name;
+        record.project = label.project;
+        record.tags = label.tags;
+        record.note = form.value(1).to_string();
+        Ok(HistoryChange::Update(record))
+    }
+
+    fn added_session(&self, form: &Form) -> Result<HistoryChange, String> {
+        let start = form.value(0);
+        let started_at = NaiveDateTime::parse_from_str(start, TIME_FORMAT)
+            .ok()
+            .or_else(|| {
+                NaiveTime::parse_from_str(start, "%H:%M")
+                    .ok()
+                    .map(|time| Local::now().date_naive().and_time(time))
+            })
+            .and_then(|start| Local.from_local_datetime(&start).earliest())
+            .ok_or_else(|| format!("invalid start time '{start}'"))?;
+        let minutes = form.value(1);
+        let length = minutes
+            .parse::<u64>()
+            .ok()
+            .filter(|minutes| *minutes > 0)
+            .map(|minutes| Duration::from_secs(minutes * 60))
+            .ok_or_else(|| format!("invalid number of minutes '{minutes}'"))?;
+        let ended_at = started_at
+            + chrono::Duration::from_std(length).map_err(|_| "too many minutes".to_string())?;
+        if ended_at > Local::now() {
+            return Err("the session would end in the future".to_string());
+        }
+
+        let record = SessionRecord::new(
+            SessionKind::Work,
+            form.value(2),
+            started_at,
+            ended_at,
+            length,
+            length,
+            true,
+        );
+        if self
+            .sessions
+            .iter()
+            .any(|existing| existing.overlaps(&record))
+        {
+            return Err("it overlaps a recorded work session".to_string());
+        }
+        Ok(HistoryChange::Insert(record))
+    }
+
+    fn selected_session(&self) -> Option<&SessionRecord> {
+        self.visible
+            .get(self.selected)
+            .map(|index| &self.sessions[*index])
+    }
+
+    /// Show only the sessions whose date, type, name, project, tags or note
+    /// contain the filter text, ignoring case.
+    fn apply_filter(&mut self) {
+        let filter = self.filter.value().trim().to_lowercase();
+        self.visible = self
+            .sessions
+            .iter()
+            .enumerate()
+            .filter(|(_, record)| {
+                filter.is_empty()
+                    || [
+                        record.started_at.format(TIME_FORMAT).to_string(),
+                        record.kind.as_str().to_string(),
+                        record.label(),
+                        record.note.clone(),
+                    ]
+                    .iter()
+                    .any(|text| text.to_lowercase().contains(&filter))
+            })
+            .map(|(index, _)| index)
+            .collect();
+        self.selected = self.selected.min(self.visible.len().saturating_sub(1));
+    }
+
+    /// Render the table of sessions, with the filter in its title.
+    fn render_table(&self, frame: &mut Frame, area: Rect) {
+        let title = if matches!(self.mode, Mode::Filter) || !self.filter.value().is_empty() {
+            format!(
+                "Session History · {} of {} sessions",
+                self.visible.len(),
+                self.sessions.len()
+            )
+        } else {
+            format!("Session History · {} sessions", self.sessions.len())
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(title);
+        let mut inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if matches!(self.mode, Mode::Filter) || !self.filter.value().is_empty() {
+            let filter_area = Rect { height: 1, ..inner };
+            frame.render_widget(
+                Paragraph::new("Filter: ").style(Style::default().fg(Color::Yellow)),
+                filter_area,
+            );
+            let input_area = Rect {
+                x: filter_area.x + 8,
+                width: filter_area.width.saturating_sub(8),
+                ..filter_area
+            };
+            self.filter.render(
+                frame,
+                input_area,
+                Style::default().fg(Color::White),
+                matches!(self.mode, Mode::Filter),
+            );
+            inner.y += 1;
+            inner.height = inner.height.saturating_sub(1);
+        }
+
+        if self.visible.is_empty() {
+            let message = if self.sessions.is_empty() {
+                "No sessions recorded yet · [A] to add one"
+            } else {
+                "No sessions match the filter"
+            };
+            frame.render_widget(
+                Paragraph::new(message).style(Style::default().fg(Color::Gray)),
+                inner,
+            );
+            return;
+        }
+
+        let header = Row::new(["Started", "Type", "Time", "Status", "Name", "Tags", "Note"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = self
+            .visible
+            .iter()
+            .map(|index| Self::row(&self.sessions[*index]));
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(16),
+                Constraint::Length(11),
+                Constraint::Length(7),
+                Constraint::Length(10),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .highlight_symbol("› ")
+        .highlight_style(Style::default().bg(Color::DarkGray));
+
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, inner, &mut state);
+    }
+
+    /// Where the session rows are drawn, below the filter and the table header.
+    fn rows_area(&self, area: Rect) -> Rect {
+        let footer = if area.height >= 12 { 3 } else { 0 };
+        let mut rows = Block::default().borders(Borders::ALL).inner(Rect {
+            height: area.height.saturating_sub(footer),
+            ..area
+        });
+        let filter =
+            u16::from(matches!(self.mode, Mode::Filter) || !self.filter.value().is_empty());
+        rows.y += filter + 1;
+        rows.height = rows.height.saturating_sub(filter + 1);
+        rows
+    }
+
+    /// A table row for a session.
+    fn row(record: &SessionRecord) -> Row<'static> {
+        let color = if record.kind == SessionKind::Work {
+            Color::Red
+        } else {
+            Color::Green
+        };
+        let completed = if record.completed { "✓" } else { "✗" };
+        let mut status = completed.to_string();
+        if record.pauses > 0 {
+            status = format!("{status} ⏸{}", record.pauses);
+        }
+        if record.adjusted_secs != 0 {
+            status = format!("{status} {:+}m", record.adjusted_secs / 60);
+        }
+        let chips = crate::ui::label_chips(record.project.as_deref(), &record.tags);
+
+        Row::new([
+            Cell::from(record.started_at.format(TIME_FORMAT).to_string()),
+            Cell::from(Span::styled(
+                record.kind.as_str(),
+                Style::default().fg(color),
+            )),
+            Cell::from(format!(
+                "{}/{}m",
+                record.actual_secs / 60,
+                record.planned_secs / 60
+            )),
+            Cell::from(status),
+            Cell::from(Span::styled(
+                record.name.clone(),
+                Style::default().fg(Color::Cyan),
+            )),
+            // Skip the space the chips start with
+            Cell::from(Line::from(chips.into_iter().skip(1).collect::<Vec<_>>())),
+            Cell::from(Span::styled(
+                record.note.clone(),
+                Style::default()
+                    .fg(Color::Gray)
+                    .add_modifier(Modifier::ITALIC),
+            )),
+        ])
+    }
+
+    /// Render the footer with the keys of the current mode.
+    fn render_footer(&self, frame: &mut Frame, area: Rect) {
+        let keys = match self.mode {
+            Mode::Filter => "Type to filter | [Enter] Done | [Esc] Clear",
+            _ => "[↑↓] Move | [/] Filter | [E] Edit | [D] Delete | [A] Add | [Esc] Back",
+        };
+        let footer = Paragraph::new(keys)
+            .style(Style::default().fg(Color::Gray))
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded),
+            );
+        frame.render_widget(footer, area);
+    }
+
+    /// Render a form in a box in the middle of the screen.
+    fn render_form(frame: &mut Frame, title: &str, form: &Form, area: Rect) {
+        let width = (area.width * 7 / 10).clamp(30, 70).min(area.width);
+        let height = form.height().min(area.height);
+        let form_area = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        frame.render_widget(Clear, form_area);
+        let block = Block::default()
+            .style(Style::default().bg(Color::Black))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .border_type(BorderType::Rounded)
+            .title(title.to_string());
+        let inner = block.inner(form_area);
+        frame.render_widget(block, form_area);
+
+        let mut constraints = vec![Constraint::Length(3); form.fields.len()];
+        constraints.push(Constraint::Length(1));
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(inner);
+
+        for (index, (label, input)) in form.fields.iter().enumerate() {
+            let focused = index == form.focus;
+            render_input(
+                frame,
+                chunks[index],
+                input,
+                field_block(label, focused),
+                focused,
+            );
+        }
+
+        let status = form.error.as_ref().map_or_else(
+            || {
+                Paragraph::new("Tab to switch • Enter to save • Esc to cancel")
+                    .style(Style::default().fg(Color::Gray))
+            },
+            |error| Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red)),
+        );
+        frame.render_widget(
+            status.alignment(ratatui::layout::Alignment::Center),
+            chunks[form.fields.len()],
+        );
+    }
+
+    /// Ask for confirmation before deleting the selected session.
+    fn render_confirm_delete(&self, frame: &mut Frame, area: Rect) {
+        let Some(record) = self.selected_session() else {
+            return;
+        };
+        let question = format!(
+            "Delete the {} session of {}? [y/N]",
+            record.kind.as_str(),
+            record.started_at.format(TIME_FORMAT)
+        );
+        render_confirm(frame, area, &question);
+    }
+}
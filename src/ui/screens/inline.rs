@@ -0,0 +1,94 @@
+//! Inline screen implementation for the Pomodoro timer application.
+//!
+//! This screen is used with `--inline`, where tomat only occupies a few lines
+//! at the bottom of the shell. It shows a compact timer line, a progress bar
+//! and key hints, dropping the latter two when the viewport is too short.
+
+use ratatui::{
+    layout::{Rect, Size},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{LineGauge, Paragraph},
+    Frame,
+};
+
+use super::{Screen, ScreenId};
+use crate::data::TimerData;
+
+/// Inline screen that displays a compact timer and progress bar.
+pub struct InlineScreen;
+
+impl Screen for InlineScreen {
+    fn id(&self) -> ScreenId {
+        ScreenId::Timer
+    }
+
+    fn render(&self, frame: &mut Frame, timer_data: &TimerData, area: Rect) {
+        // One line per element, dropping the bottom ones when the viewport is short
+        let row = |offset: u16| Rect::new(area.x, area.y + offset, area.width, 1);
+
+        Self::render_status(frame, timer_data, row(0));
+        if area.height >= 2 {
+            Self::render_progress(frame, timer_data, row(1));
+        }
+        if area.height >= 3 {
+            Self::render_hints(frame, row(2));
+        }
+    }
+
+    fn min_size(&self) -> Size {
+        Size::new(10, 1)
+    }
+}
+
+impl InlineScreen {
+    /// Render the timer, session type and session name on one line.
+    fn render_status(frame: &mut Frame, timer_data: &TimerData, area: Rect) {
+        let color = if timer_data.is_paused {
+            Color::Yellow
+        } else if timer_data.is_running {
+            Color::Red
+        } else {
+            Color::Blue
+        };
+
+        let mut spans = vec![
+            Span::raw("🍅 "),
+            Span::styled(
+                timer_data.compact_line(),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+        ];
+        if !timer_data.session_name.is_empty() {
+            spans.push(Span::raw(" · "));
+            spans.push(Span::styled(
+                timer_data.session_name.clone(),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+        spans.push(Span::styled(
+            format!(" · {} completed", timer_data.sessions_completed),
+            Style::default().fg(Color::Gray),
+        ));
+
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    /// Render the session progress as a single-line gauge.
+    fn render_progress(frame: &mut Frame, timer_data: &TimerData, area: Rect) {
+        let gauge = LineGauge::default()
+            .filled_style(Style::default().fg(Color::Cyan))
+            .unfilled_style(Style::default().fg(Color::DarkGray))
+            .ratio(timer_data.progress_percentage() / 100.0)
+            .label(format!("{:>5.1}%", timer_data.progress_percentage()));
+        frame.render_widget(gauge, area);
+    }
+
+    /// Render the key hints line.
+    fn render_hints(frame: &mut Frame, area: Rect) {
+        let hints =
+            Paragraph::new("[Space] Start/Pause | [S] Skip | [R] Reset | [N] Name | [Q] Quit")
+                .style(Style::default().fg(Color::Gray));
+        frame.render_widget(hints, area);
+    }
+}
@@ -3,21 +3,153 @@
 //! This module contains all the different screen types that can be rendered
 //! in the application, each implementing the Screen trait.
 
-use ratatui::{layout::Rect, Frame};
-use std::any::Any;
+use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::{
+    layout::{Rect, Size},
+    Frame,
+};
 
+use crate::action::Action;
+use crate::config::Config;
 use crate::data::TimerData;
+use crate::history::History;
+use crate::streaks::Streaks;
+use history::HistoryChange;
+use settings::Settings;
 
 pub mod clock;
 pub mod fullscreen;
 pub mod help;
+pub mod history;
+pub mod inline;
 pub mod normal;
+pub mod settings;
+pub mod stats;
+
+/// Identifies a screen, for navigation and the tab bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenId {
+    /// The main timer view, full-size or inline.
+    Timer,
+    Fullscreen,
+    Clock,
+    Stats,
+    History,
+    Settings,
+    Help,
+}
+
+impl ScreenId {
+    /// Every screen, in tab bar order.
+    pub const ALL: [Self; 7] = [
+        Self::Timer,
+        Self::Fullscreen,
+        Self::Clock,
+        Self::Stats,
+        Self::History,
+        Self::Settings,
+        Self::Help,
+    ];
+
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::Timer => "Timer",
+            Self::Fullscreen => "Focus",
+            Self::Clock => "Clock",
+            Self::Stats => "Stats",
+            Self::History => "History",
+            Self::Settings => "Settings",
+            Self::Help => "Help",
+        }
+    }
+
+    /// Key that opens the screen; the timer is reached with Esc.
+    pub const fn key(self) -> Option<char> {
+        match self {
+            Self::Timer => None,
+            Self::Fullscreen => Some('f'),
+            Self::Clock => Some('c'),
+            Self::Stats => Some('t'),
+            Self::History => Some('l'),
+            Self::Settings => Some('o'),
+            Self::Help => Some('h'),
+        }
+    }
+
+    /// The screen opened by `key`, if any.
+    pub fn from_key(key: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|id| id.key() == Some(key))
+    }
+
+    /// The next or previous screen in the tab bar, wrapping around.
+    pub fn cycle(self, forward: bool) -> Self {
+        let count = Self::ALL.len();
+        let index = Self::ALL.iter().position(|id| *id == self).unwrap_or(0);
+        let next = if forward {
+            (index + 1) % count
+        } else {
+            (index + count - 1) % count
+        };
+        Self::ALL[next]
+    }
+}
+
+/// A change a screen asks the application to make.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScreenRequest {
+    /// Change the session history.
+    EditHistory(HistoryChange),
+    /// Use these settings from now on.
+    ApplySettings(Settings),
+}
+
+/// What a screen did with a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScreenKey {
+    /// The key is not used by the screen, and goes on to the global keys.
+    Ignored,
+    Handled,
+    /// The key asks for a change outside the screen.
+    Request(ScreenRequest),
+}
 
 /// Trait that all screens must implement for rendering.
 pub trait Screen {
+    /// Which screen this is.
+    fn id(&self) -> ScreenId;
+
     /// Render the screen content to the given frame.
     fn render(&self, frame: &mut Frame, timer_data: &TimerData, area: Rect);
 
-    /// Return a reference to the screen as Any for type checking.
-    fn as_any(&self) -> &dyn Any;
+    /// Smallest area the screen can render into.
+    ///
+    /// Below this size the compact single-line view is shown instead.
+    fn min_size(&self) -> Size {
+        Size::new(1, 1)
+    }
+
+    /// Handle a key before the global keys get it.
+    fn handle_key(&mut self, _key: KeyEvent) -> ScreenKey {
+        ScreenKey::Ignored
+    }
+
+    /// Handle a click or scroll inside the screen's `area`, returning the
+    /// action it stands for, if any.
+    fn handle_mouse(
+        &mut self,
+        _mouse: MouseEvent,
+        _timer_data: &TimerData,
+        _area: Rect,
+    ) -> Option<Action> {
+        None
+    }
+
+    /// Insert pasted text into the field being typed into, if any.
+    fn handle_paste(&mut self, _text: &str) {}
+
+    /// Bring the screen up to date after the session history changed.
+    fn history_changed(&mut self, _history: &History, _streaks: Streaks) {}
+
+    /// Bring the screen up to date after the configuration changed.
+    fn config_changed(&mut self, _config: &Config) {}
 }
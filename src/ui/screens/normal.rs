@@ -3,45 +3,211 @@
 //! This screen displays the full timer interface with header, timer area,
 //! statistics, and controls in a structured layout.
 
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Position, Rect, Size},
     style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, BorderType, Borders, Gauge, Paragraph},
     Frame,
 };
-use std::any::Any;
 
-use super::Screen;
+use super::{Screen, ScreenId};
+use crate::action::Action;
 use crate::data::TimerData;
 
+/// A control hint and the action clicking it runs.
+type Control = (&'static str, Action);
+
+/// Text before the full control hints.
+const CONTROLS_PREFIX: &str = "Controls: ";
+
+/// Text between two control hints.
+const CONTROLS_SEPARATOR: &str = " | ";
+
+const RUNNING_CONTROLS: &[Control] = &[
+    ("[Space] Pause/Resume", Action::StartPause),
+    ("[R] Reset", Action::Reset),
+    ("[S] Skip", Action::Skip),
+    ("[N] Name Session", Action::Name),
+    ("[F] Fullscreen", Action::Show(ScreenId::Fullscreen)),
+    ("[H] Help", Action::Show(ScreenId::Help)),
+    ("[C] Clock", Action::Show(ScreenId::Clock)),
+    ("[Q] Quit", Action::Quit),
+];
+
+const PAUSED_CONTROLS: &[Control] = &[
+    ("[Space/Enter] Resume", Action::StartPause),
+    ("[R] Reset", Action::Reset),
+    ("[N] Name Session", Action::Name),
+    ("[F] Fullscreen", Action::Show(ScreenId::Fullscreen)),
+    ("[H] Help", Action::Show(ScreenId::Help)),
+    ("[C] Clock", Action::Show(ScreenId::Clock)),
+    ("[Q] Quit", Action::Quit),
+];
+
+const IDLE_CONTROLS: &[Control] = &[
+    ("[Space/Enter] Start Timer", Action::StartPause),
+    ("[R] Reset", Action::Reset),
+    ("[N] Name Session", Action::Name),
+    ("[F] Fullscreen", Action::Show(ScreenId::Fullscreen)),
+    ("[H] Help", Action::Show(ScreenId::Help)),
+    ("[C] Clock", Action::Show(ScreenId::Clock)),
+    ("[Q] Quit", Action::Quit),
+];
+
+/// Hints for a narrow terminal.
+const SHORT_CONTROLS: &[Control] = &[
+    ("[Space] ⏯", Action::StartPause),
+    ("[R] Reset", Action::Reset),
+    ("[S] Skip", Action::Skip),
+    ("[N] Name", Action::Name),
+    ("[H] Help", Action::Show(ScreenId::Help)),
+    ("[Q] Quit", Action::Quit),
+];
+
+/// Hints for a very narrow terminal.
+const SHORTEST_CONTROLS: &[Control] = &[
+    ("[Space] ⏯", Action::StartPause),
+    ("[H] Help", Action::Show(ScreenId::Help)),
+    ("[Q] Quit", Action::Quit),
+];
+
 /// Normal screen that displays the full timer interface.
 pub struct NormalScreen;
 
+/// Panels of the normal screen, listed top to bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Panel {
+    Header,
+    Timer,
+    Session,
+    Progress,
+    Statistics,
+    CurrentSession,
+    Controls,
+}
+
+impl Panel {
+    /// All panels in display order.
+    const ALL: [Self; 7] = [
+        Self::Header,
+        Self::Timer,
+        Self::Session,
+        Self::Progress,
+        Self::Statistics,
+        Self::CurrentSession,
+        Self::Controls,
+    ];
+
+    /// Panels in the order they are dropped when the terminal gets too short.
+    /// The timer itself is never dropped.
+    const DROP_ORDER: [Self; 6] = [
+        Self::CurrentSession,
+        Self::Statistics,
+        Self::Header,
+        Self::Controls,
+        Self::Session,
+        Self::Progress,
+    ];
+
+    /// Every panel is a bordered box holding a single line of content.
+    const HEIGHT: u16 = 3;
+}
+
 impl Screen for NormalScreen {
+    fn id(&self) -> ScreenId {
+        ScreenId::Timer
+    }
+
     fn render(&self, frame: &mut Frame, timer_data: &TimerData, area: Rect) {
-        // Create the main layout matching the original design
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3), // Header
-                Constraint::Length(6), // Timer area (3 + 3 for timer and session)
-                Constraint::Min(0),    // Stats (flexible)
-                Constraint::Length(3), // Controls
-            ])
-            .split(area);
+        for (panel, chunk) in Self::layout(area) {
+            match panel {
+                Some(Panel::Header) => Self::render_header(frame, timer_data, chunk),
+                Some(Panel::Timer) => Self::render_timer(frame, timer_data, chunk),
+                Some(Panel::Session) => Self::render_session(frame, timer_data, chunk),
+                Some(Panel::Progress) => Self::render_progress(frame, timer_data, chunk),
+                Some(Panel::Statistics) => Self::render_statistics(frame, timer_data, chunk),
+                Some(Panel::CurrentSession) => {
+                    Self::render_current_session(frame, timer_data, chunk);
+                }
+                Some(Panel::Controls) => Self::render_controls(frame, timer_data, chunk),
+                None => {}
+            }
+        }
+    }
 
-        Self::render_header(frame, timer_data, chunks[0]);
-        Self::render_timer_area(frame, timer_data, chunks[1]);
-        Self::render_stats_area(frame, timer_data, chunks[2]);
-        Self::render_controls(frame, timer_data, chunks[3]);
+    fn min_size(&self) -> Size {
+        Size::new(20, Panel::HEIGHT)
     }
 
-    fn as_any(&self) -> &dyn Any {
-        self
+    fn handle_mouse(
+        &mut self,
+        mouse: MouseEvent,
+        timer_data: &TimerData,
+        area: Rect,
+    ) -> Option<Action> {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return None;
+        }
+        let position = Position::new(mouse.column, mouse.row);
+        let (panel, chunk) = Self::layout(area)
+            .into_iter()
+            .find(|(_, chunk)| chunk.contains(position))?;
+        match panel? {
+            Panel::Timer | Panel::Progress => Some(Action::StartPause),
+            Panel::Session | Panel::CurrentSession => Some(Action::Name),
+            Panel::Controls => Self::control_at(timer_data, chunk, mouse.column),
+            Panel::Header | Panel::Statistics => None,
+        }
     }
 }
 
 impl NormalScreen {
+    /// Place the visible panels in `area`, top to bottom.
+    ///
+    /// Fixed-height panels, with the current session box (or a spacer when it
+    /// was dropped) absorbing the remaining space so controls stay at the bottom.
+    fn layout(area: Rect) -> Vec<(Option<Panel>, Rect)> {
+        let panels = Self::visible_panels(area.height);
+        let mut slots: Vec<(Option<Panel>, Constraint)> = Vec::with_capacity(panels.len() + 1);
+        for panel in &panels {
+            if *panel == Panel::Controls && !panels.contains(&Panel::CurrentSession) {
+                slots.push((None, Constraint::Min(0)));
+            }
+            let constraint = if *panel == Panel::CurrentSession {
+                Constraint::Min(Panel::HEIGHT)
+            } else {
+                Constraint::Length(Panel::HEIGHT)
+            };
+            slots.push((Some(*panel), constraint));
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(slots.iter().map(|(_, constraint)| *constraint))
+            .split(area);
+        slots
+            .iter()
+            .map(|(panel, _)| *panel)
+            .zip(chunks.iter().copied())
+            .collect()
+    }
+
+    /// Pick the panels that fit in the given height, dropping the least
+    /// important ones first.
+    fn visible_panels(height: u16) -> Vec<Panel> {
+        let mut panels = Panel::ALL.to_vec();
+        for dropped in Panel::DROP_ORDER {
+            let needed = u16::try_from(panels.len()).unwrap_or(u16::MAX) * Panel::HEIGHT;
+            if needed <= height {
+                break;
+            }
+            panels.retain(|panel| *panel != dropped);
+        }
+        panels
+    }
+
     /// Render the header section with title and status.
     fn render_header(frame: &mut Frame, timer_data: &TimerData, area: Rect) {
         let title = if timer_data.is_paused {
@@ -71,13 +237,8 @@ impl NormalScreen {
         frame.render_widget(header, area);
     }
 
-    /// Render the timer area with time remaining and session info.
-    fn render_timer_area(frame: &mut Frame, timer_data: &TimerData, area: Rect) {
-        let timer_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Length(3), Constraint::Length(3)])
-            .split(area);
-
+    /// Render the time remaining box.
+    fn render_timer(frame: &mut Frame, timer_data: &TimerData, area: Rect) {
         // Time remaining display
         let time_text = if timer_data.remaining_time.as_secs() == 0 {
             String::from("--:--")
@@ -87,7 +248,7 @@ impl NormalScreen {
 
         let status_text = if timer_data.is_paused {
             " (PAUSED)"
-        } else if !timer_data.is_running {
+        } else if !timer_data.is_running && area.width >= 40 {
             " (PRESS SPACE/ENTER TO START)"
         } else {
             ""
@@ -103,15 +264,23 @@ impl NormalScreen {
                     .border_type(BorderType::Rounded)
                     .title("Time Remaining"),
             );
-        frame.render_widget(timer_display, timer_chunks[0]);
+        frame.render_widget(timer_display, area);
+    }
 
-        // Session info
-        let session_text: String = if timer_data.naming_mode {
-            "📝 NAMING MODE - Type session name...".to_string()
-        } else if timer_data.session_name.is_empty() {
-            "Press [N] to name this session".to_string()
+    /// Render the session name box, with the project and tags as chips.
+    fn render_session(frame: &mut Frame, timer_data: &TimerData, area: Rect) {
+        let labelled = timer_data.project.is_some() || !timer_data.tags.is_empty();
+        let session_text = if timer_data.naming_mode {
+            Line::from("📝 NAMING MODE - Type session name...")
+        } else if timer_data.session_name.is_empty() && !labelled {
+            Line::from("Press [N] to name this session")
         } else {
-            format!("📝 {}", timer_data.session_name)
+            let mut spans = vec![Span::raw(format!("📝 {}", timer_data.session_name))];
+            spans.extend(crate::ui::label_chips(
+                timer_data.project.as_deref(),
+                &timer_data.tags,
+            ));
+            Line::from(spans)
         };
         let session_display = Paragraph::new(session_text)
             .style(Style::default().fg(Color::Cyan))
@@ -122,21 +291,24 @@ impl NormalScreen {
                     .border_type(BorderType::Rounded)
                     .title("Session"),
             );
-        frame.render_widget(session_display, timer_chunks[1]);
+        frame.render_widget(session_display, area);
     }
 
-    /// Render the statistics area with progress gauge and session info.
-    fn render_stats_area(frame: &mut Frame, timer_data: &TimerData, area: Rect) {
-        let stats_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3), // Progress gauge
-                Constraint::Length(3), // Statistics
-                Constraint::Min(0),    // Current session info
-            ])
+    /// Render the progress gauge, with goal gauges beside it when goals are set
+    /// and the terminal is wide enough.
+    fn render_progress(frame: &mut Frame, timer_data: &TimerData, area: Rect) {
+        let goals = &timer_data.goals;
+        let goal_count = u32::from(goals.daily.is_some()) + u32::from(goals.weekly.is_some());
+        let show_goals = goal_count > 0 && area.width >= 60;
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(if show_goals {
+                vec![Constraint::Ratio(1, goal_count + 1); goal_count as usize + 1]
+            } else {
+                vec![Constraint::Min(0)]
+            })
             .split(area);
 
-        // Progress gauge
         let progress = timer_data.progress_percentage() / 100.0;
         let progress_gauge = Gauge::default()
             .block(
@@ -148,10 +320,67 @@ impl NormalScreen {
             .gauge_style(Style::default().fg(Color::Cyan))
             .ratio(progress)
             .label(format!("{:.1}%", timer_data.progress_percentage()));
-        frame.render_widget(progress_gauge, stats_chunks[0]);
+        frame.render_widget(progress_gauge, chunks[0]);
 
-        // Statistics
-        let stats_text = format!("Completed Sessions: {}", timer_data.sessions_completed);
+        if show_goals {
+            let mut goal_chunks = chunks.iter().skip(1);
+            if let (Some((done, target)), Some(chunk)) = (goals.daily, goal_chunks.next()) {
+                let label = format!("{done}/{target} pomodoros");
+                Self::render_goal(
+                    frame,
+                    "Daily Goal",
+                    f64::from(done),
+                    f64::from(target),
+                    label,
+                    *chunk,
+                );
+            }
+            if let (Some((done, target)), Some(chunk)) = (goals.weekly, goal_chunks.next()) {
+                let label = format!("{done:.1}/{target}h");
+                Self::render_goal(frame, "Weekly Goal", done, target, label, *chunk);
+            }
+        }
+    }
+
+    /// Render a single goal gauge, turning green once the goal is reached.
+    fn render_goal(
+        frame: &mut Frame,
+        title: &str,
+        done: f64,
+        target: f64,
+        label: String,
+        area: Rect,
+    ) {
+        let ratio = if target > 0.0 {
+            (done / target).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let color = if ratio >= 1.0 {
+            Color::Green
+        } else {
+            Color::Magenta
+        };
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(title.to_string()),
+            )
+            .gauge_style(Style::default().fg(color))
+            .ratio(ratio)
+            .label(label);
+        frame.render_widget(gauge, area);
+    }
+
+    /// Render the statistics box.
+    fn render_statistics(frame: &mut Frame, timer_data: &TimerData, area: Rect) {
+        let stats_text = format!(
+            "Completed Sessions: {} | Streak: {}",
+            timer_data.sessions_completed,
+            timer_data.streaks.describe()
+        );
         let stats_display = Paragraph::new(stats_text)
             .block(
                 Block::default()
@@ -160,9 +389,11 @@ impl NormalScreen {
                     .title("Statistics"),
             )
             .alignment(ratatui::layout::Alignment::Center);
-        frame.render_widget(stats_display, stats_chunks[1]);
+        frame.render_widget(stats_display, area);
+    }
 
-        // Current session info
+    /// Render the current session start time.
+    fn render_current_session(frame: &mut Frame, timer_data: &TimerData, area: Rect) {
         let session_info = timer_data.session_start_time.as_ref().map_or_else(
             || "Session started: --:--:--".to_string(),
             |start_time| format!("Session started: {start_time}"),
@@ -175,30 +406,78 @@ impl NormalScreen {
                     .title("Current Session"),
             )
             .alignment(ratatui::layout::Alignment::Center);
-        frame.render_widget(session_display, stats_chunks[2]);
+        frame.render_widget(session_display, area);
     }
 
-    /// Render the controls section with available keyboard shortcuts.
-    fn render_controls(frame: &mut Frame, timer_data: &TimerData, area: Rect) {
-        let controls_text = if timer_data.is_running {
-            "Controls: [Space] Pause/Resume | [R] Reset | [S] Skip | [N] Name Session | [F] Fullscreen | [H] Help | [C] Clock | [Q] Quit"
+    /// The hints of the controls box, for a box `width` columns wide, and the
+    /// text shown before them.
+    ///
+    /// Falls back to shorter hints when the full ones do not fit.
+    fn controls(timer_data: &TimerData, width: u16) -> (&'static str, &'static [Control]) {
+        let controls = if timer_data.is_running {
+            RUNNING_CONTROLS
         } else if timer_data.session_start_time.is_some() {
             // Timer has started but is currently paused
-            "Controls: [Space/Enter] Resume | [R] Reset | [N] Name Session | [F] Fullscreen | [H] Help | [C] Clock | [Q] Quit"
+            PAUSED_CONTROLS
         } else {
             // Timer has never started
-            "Controls: [Space/Enter] Start Timer | [R] Reset | [N] Name Session | [F] Fullscreen | [H] Help | [C] Clock | [Q] Quit"
+            IDLE_CONTROLS
         };
 
-        let controls = Paragraph::new(controls_text)
-            .style(Style::default().fg(Color::Gray))
-            .alignment(ratatui::layout::Alignment::Center)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .title("Controls"),
-            );
-        frame.render_widget(controls, area);
+        let inner_width = usize::from(width.saturating_sub(2));
+        if Self::controls_line(CONTROLS_PREFIX, controls).width() <= inner_width {
+            (CONTROLS_PREFIX, controls)
+        } else if Self::controls_line("", SHORT_CONTROLS).width() <= inner_width {
+            ("", SHORT_CONTROLS)
+        } else {
+            ("", SHORTEST_CONTROLS)
+        }
+    }
+
+    /// The line showing `controls` after `prefix`.
+    fn controls_line(prefix: &'static str, controls: &[Control]) -> Line<'static> {
+        let mut spans = vec![Span::raw(prefix)];
+        for (index, (label, _)) in controls.iter().enumerate() {
+            if index > 0 {
+                spans.push(Span::raw(CONTROLS_SEPARATOR));
+            }
+            spans.push(Span::raw(*label));
+        }
+        Line::from(spans).centered()
+    }
+
+    /// The action of the control hint under `column` in the controls box.
+    fn control_at(timer_data: &TimerData, area: Rect, column: u16) -> Option<Action> {
+        let (prefix, controls) = Self::controls(timer_data, area.width);
+        let width = |text: &str| u16::try_from(Line::from(text).width()).unwrap_or(u16::MAX);
+        let inner_width = area.width.saturating_sub(2);
+        let line_width =
+            u16::try_from(Self::controls_line(prefix, controls).width()).unwrap_or(u16::MAX);
+        // Where the centred line starts, as `Line` places it
+        let mut x = area.x + 1 + inner_width.saturating_sub(line_width) / 2 + width(prefix);
+        for (label, action) in controls {
+            let label_width = width(label);
+            if (x..x + label_width).contains(&column) {
+                return Some(*action);
+            }
+            x += label_width + width(CONTROLS_SEPARATOR);
+        }
+        None
+    }
+
+    /// Render the controls section with available keyboard shortcuts.
+    fn render_controls(frame: &mut Frame, timer_data: &TimerData, area: Rect) {
+        let (prefix, controls) = Self::controls(timer_data, area.width);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title("Controls");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        frame.render_widget(
+            Self::controls_line(prefix, controls).style(Style::default().fg(Color::Gray)),
+            inner,
+        );
     }
 }
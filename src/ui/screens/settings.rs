@@ -0,0 +1,341 @@
+//! Settings screen implementation for the Pomodoro timer application.
+//!
+//! This screen changes the session lengths, the auto-start policies, the
+//! theme and the notifications while the timer runs. Changes apply from the
+//! next session and can be saved to the configuration file.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect, Size},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::{Screen, ScreenId, ScreenKey, ScreenRequest};
+use crate::config::{Config, NotificationsConfig, Theme, TimerConfig, UiConfig};
+use crate::data::TimerData;
+
+/// The sections of the configuration the screen edits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    pub timer: TimerConfig,
+    pub ui: UiConfig,
+    pub notifications: NotificationsConfig,
+}
+
+impl Settings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            timer: config.timer.clone(),
+            ui: config.ui.clone(),
+            notifications: config.notifications.clone(),
+        }
+    }
+
+    /// Copy the settings into `config`.
+    pub fn apply_to(&self, config: &mut Config) {
+        config.timer = self.timer.clone();
+        config.ui = self.ui.clone();
+        config.notifications = self.notifications.clone();
+    }
+}
+
+/// A line of the settings list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Work,
+    ShortBreak,
+    LongBreak,
+    LongBreakInterval,
+    AutoStartBreaks,
+    AutoStartWork,
+    Theme,
+    Bell,
+    Message,
+}
+
+impl Field {
+    const ALL: [Self; 9] = [
+        Self::Work,
+        Self::ShortBreak,
+        Self::LongBreak,
+        Self::LongBreakInterval,
+        Self::AutoStartBreaks,
+        Self::AutoStartWork,
+        Self::Theme,
+        Self::Bell,
+        Self::Message,
+    ];
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Work => "Work session",
+            Self::ShortBreak => "Short break",
+            Self::LongBreak => "Long break",
+            Self::LongBreakInterval => "Long break every",
+            Self::AutoStartBreaks => "Start breaks automatically",
+            Self::AutoStartWork => "Start work automatically",
+            Self::Theme => "Theme",
+            Self::Bell => "Ring the bell when a session ends",
+            Self::Message => "Announce the next session",
+        }
+    }
+
+    /// Smallest and largest value of a numeric setting.
+    const fn range(self) -> (u32, u32) {
+        match self {
+            Self::Work => (1, 180),
+            Self::ShortBreak => (1, 60),
+            Self::LongBreak => (1, 120),
+            _ => (1, 12),
+        }
+    }
+}
+
+/// Screen for changing the settings while the timer runs.
+pub struct SettingsScreen {
+    settings: Settings,
+    selected: usize,
+    /// Result of the last save, if any, and whether it failed.
+    status: Option<(String, bool)>,
+}
+
+impl Screen for SettingsScreen {
+    fn id(&self) -> ScreenId {
+        ScreenId::Settings
+    }
+
+    fn render(&self, frame: &mut Frame, _timer_data: &TimerData, area: Rect) {
+        let show_footer = area.height >= 16;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),                                  // Settings
+                Constraint::Length(if show_footer { 3 } else { 0 }), // Footer
+            ])
+            .split(area);
+
+        self.render_settings(frame, chunks[0]);
+        if show_footer {
+            Self::render_footer(frame, chunks[1]);
+        }
+    }
+
+    fn min_size(&self) -> Size {
+        Size::new(50, 13)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ScreenKey {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            // Always let Ctrl+C through to quit
+            return ScreenKey::Ignored;
+        }
+
+        let last = Field::ALL.len() - 1;
+        let changed = match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.checked_sub(1).unwrap_or(last);
+                false
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = if self.selected == last {
+                    0
+                } else {
+                    self.selected + 1
+                };
+                false
+            }
+            KeyCode::Right | KeyCode::Char('+') => self.change(true),
+            KeyCode::Left | KeyCode::Char('-') => self.change(false),
+            KeyCode::Char(' ') | KeyCode::Enter => self.toggle(),
+            KeyCode::Char('w') => {
+                self.save();
+                false
+            }
+            _ => return ScreenKey::Ignored,
+        };
+
+        if changed {
+            self.status = None;
+            ScreenKey::Request(ScreenRequest::ApplySettings(self.settings.clone()))
+        } else {
+            ScreenKey::Handled
+        }
+    }
+
+    fn config_changed(&mut self, config: &Config) {
+        // Changes made elsewhere, such as the theme from the command palette
+        self.settings = Settings::from_config(config);
+    }
+}
+
+impl SettingsScreen {
+    pub const fn new(settings: Settings) -> Self {
+        Self {
+            settings,
+            selected: 0,
+            status: None,
+        }
+    }
+
+    /// Write the settings to the configuration file, showing where or why not.
+    fn save(&mut self) {
+        let result = Config::default_path()
+            .ok_or_else(|| "no configuration directory".into())
+            .and_then(|path| {
+                Config::update_file(&path, |config| self.settings.apply_to(config))?;
+                Ok::<_, Box<dyn std::error::Error>>(path)
+            });
+        self.status = Some(match result {
+            Ok(path) => {
+                info!("Saved settings to {}", path.display());
+                (format!("Saved to {}", path.display()), false)
+            }
+            Err(e) => {
+                error!("Failed to save settings: {e}");
+                (format!("Failed to save: {e}"), true)
+            }
+        });
+    }
+
+    const fn field(&self) -> Field {
+        Field::ALL[self.selected]
+    }
+
+    /// Step the selected setting up or down. Returns whether it changed.
+    fn change(&mut self, up: bool) -> bool {
+        let field = self.field();
+        let (min, max) = field.range();
+        let timer = &mut self.settings.timer;
+        let value = match field {
+            Field::Work => &mut timer.work_minutes,
+            Field::ShortBreak => &mut timer.short_break_minutes,
+            Field::LongBreak => &mut timer.long_break_minutes,
+            Field::LongBreakInterval => &mut timer.long_break_interval,
+            Field::Theme => {
+                let themes = Theme::ALL;
+                let index = themes
+                    .iter()
+                    .position(|theme| *theme == self.settings.ui.theme)
+                    .unwrap_or(0);
+                let index = if up {
+                    (index + 1) % themes.len()
+                } else {
+                    (index + themes.len() - 1) % themes.len()
+                };
+                self.settings.ui.theme = themes[index];
+                return true;
+            }
+            _ => return self.toggle(),
+        };
+        let new_value = if up {
+            value.saturating_add(1).min(max)
+        } else {
+            value.saturating_sub(1).max(min)
+        };
+        let changed = new_value != *value;
+        *value = new_value;
+        changed
+    }
+
+    /// Flip the selected setting if it is on or off. Returns whether it changed.
+    fn toggle(&mut self) -> bool {
+        let flag = match self.field() {
+            Field::AutoStartBreaks => &mut self.settings.timer.auto_start_breaks,
+            Field::AutoStartWork => &mut self.settings.timer.auto_start_work,
+            Field::Bell => &mut self.settings.notifications.bell,
+            Field::Message => &mut self.settings.notifications.message,
+            Field::Theme => return self.change(true),
+            _ => return false,
+        };
+        *flag = !*flag;
+        true
+    }
+
+    /// A setting's value as shown in the list.
+    fn value(&self, field: Field) -> String {
+        let timer = &self.settings.timer;
+        let on_off = |on: bool| if on { "on" } else { "off" }.to_string();
+        match field {
+            Field::Work => format!("{} min", timer.work_minutes),
+            Field::ShortBreak => format!("{} min", timer.short_break_minutes),
+            Field::LongBreak => format!("{} min", timer.long_break_minutes),
+            Field::LongBreakInterval => format!("{} sessions", timer.long_break_interval),
+            Field::AutoStartBreaks => on_off(timer.auto_start_breaks),
+            Field::AutoStartWork => on_off(timer.auto_start_work),
+            Field::Theme => self.settings.ui.theme.as_str().to_string(),
+            Field::Bell => on_off(self.settings.notifications.bell),
+            Field::Message => on_off(self.settings.notifications.message),
+        }
+    }
+
+    /// Render the list of settings and the status line under it.
+    fn render_settings(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title("Settings");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),    // Settings
+                Constraint::Length(1), // Status
+            ])
+            .split(inner);
+
+        let label_width = Field::ALL
+            .iter()
+            .map(|field| field.label().len())
+            .max()
+            .unwrap_or(0);
+        let items: Vec<ListItem> = Field::ALL
+            .iter()
+            .map(|field| {
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{:label_width$}  ", field.label())),
+                    Span::styled(
+                        format!("‹ {} ›", self.value(*field)),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                ]))
+            })
+            .collect();
+        let list = List::new(items).highlight_symbol("› ").highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        let status = match &self.status {
+            Some((message, true)) => {
+                Paragraph::new(message.as_str()).style(Style::default().fg(Color::Red))
+            }
+            Some((message, false)) => {
+                Paragraph::new(message.as_str()).style(Style::default().fg(Color::Green))
+            }
+            None => Paragraph::new("Changes apply from the next session")
+                .style(Style::default().fg(Color::Gray)),
+        };
+        frame.render_widget(status, chunks[1]);
+    }
+
+    /// Render the footer with the keys of the screen.
+    fn render_footer(frame: &mut Frame, area: Rect) {
+        let footer = Paragraph::new("[↑↓] Select | [←→] Change | [W] Save | [Esc] Back | [Q] Quit")
+            .style(Style::default().fg(Color::Gray))
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded),
+            );
+        frame.render_widget(footer, area);
+    }
+}
@@ -0,0 +1,446 @@
+//! Statistics screen implementation for the Pomodoro timer application.
+//!
+//! This screen displays a calendar heatmap of focused time per day, in the
+//! style of a contribution graph, along with the sessions of the selected day.
+//! Both can be narrowed down to a single project or tag.
+
+use chrono::{Datelike, Days, Local, Months, NaiveDate};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect, Size},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use std::collections::BTreeMap;
+
+use super::{Screen, ScreenId, ScreenKey};
+use crate::data::stats::{daily_totals, week_start, DayTotals};
+use crate::data::TimerData;
+use crate::history::{History, SessionKind, SessionRecord};
+use crate::streaks::Streaks;
+use crate::tags::LabelFilter;
+
+/// Width of the weekday labels on the left of the heatmap.
+const LABEL_WIDTH: u16 = 4;
+
+/// Width of a single week column.
+const CELL_WIDTH: u16 = 2;
+
+/// Never show more than a year of weeks.
+const MAX_WEEKS: u16 = 53;
+
+/// Height of the heatmap box: borders, month labels, seven days and the legend.
+const HEATMAP_HEIGHT: u16 = 11;
+
+/// Colours for the five intensity levels, from "nothing" to "a lot".
+const LEVEL_COLORS: [Color; 5] = [
+    Color::Indexed(237),
+    Color::Indexed(22),
+    Color::Indexed(28),
+    Color::Indexed(34),
+    Color::Indexed(40),
+];
+
+/// Glyphs standing in for the level colours in the monochrome theme.
+const LEVEL_GLYPHS: [&str; 5] = ["·", "░", "▒", "▓", "█"];
+
+/// The glyph for the heatmap level drawn in `color`, if it is one.
+pub fn level_glyph(color: Color) -> Option<&'static str> {
+    LEVEL_COLORS
+        .iter()
+        .position(|level_color| *level_color == color)
+        .map(|level| LEVEL_GLYPHS[level])
+}
+
+/// What the heatmap cells measure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Metric {
+    FocusedMinutes,
+    Pomodoros,
+}
+
+impl Metric {
+    const fn value(self, totals: &DayTotals) -> u64 {
+        match self {
+            Self::FocusedMinutes => totals.focused_minutes(),
+            Self::Pomodoros => totals.pomodoros as u64,
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::FocusedMinutes => "focused minutes",
+            Self::Pomodoros => "completed pomodoros",
+        }
+    }
+}
+
+/// Statistics screen showing a heatmap of focused time per day.
+pub struct StatsScreen {
+    totals: BTreeMap<NaiveDate, DayTotals>,
+    /// Sessions matching the filter.
+    sessions: Vec<SessionRecord>,
+    all_sessions: Vec<SessionRecord>,
+    /// Projects and tags the statistics can be filtered by.
+    filters: Vec<LabelFilter>,
+    /// Index of the filter in use, if any.
+    filter: Option<usize>,
+    today: NaiveDate,
+    selected: NaiveDate,
+    metric: Metric,
+    streaks: Streaks,
+}
+
+impl Screen for StatsScreen {
+    fn id(&self) -> ScreenId {
+        ScreenId::Stats
+    }
+
+    fn render(&self, frame: &mut Frame, _timer_data: &TimerData, area: Rect) {
+        let show_footer = area.height >= HEATMAP_HEIGHT + 6;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(HEATMAP_HEIGHT), // Heatmap
+                Constraint::Min(0),                 // Selected day
+                Constraint::Length(if show_footer { 3 } else { 0 }),
+            ])
+            .split(area);
+
+        self.render_heatmap(frame, chunks[0]);
+        self.render_day(frame, chunks[1]);
+        if show_footer {
+            Self::render_footer(frame, chunks[2]);
+        }
+    }
+
+    fn min_size(&self) -> Size {
+        Size::new(LABEL_WIDTH + CELL_WIDTH * 4 + 2, HEATMAP_HEIGHT + 3)
+    }
+
+    /// Handle navigation keys. Returns whether the key was used.
+    fn handle_key(&mut self, key: KeyEvent) -> ScreenKey {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return ScreenKey::Ignored;
+        }
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let selected = match key.code {
+            KeyCode::Left if shift => self.selected.checked_sub_months(Months::new(1)),
+            KeyCode::Right if shift => self.selected.checked_add_months(Months::new(1)),
+            KeyCode::PageUp | KeyCode::Char('<') => {
+                self.selected.checked_sub_months(Months::new(1))
+            }
+            KeyCode::PageDown | KeyCode::Char('>') => {
+                self.selected.checked_add_months(Months::new(1))
+            }
+            KeyCode::Left => self.selected.checked_sub_days(Days::new(7)),
+            KeyCode::Right => self.selected.checked_add_days(Days::new(7)),
+            KeyCode::Up => self.selected.pred_opt(),
+            KeyCode::Down => self.selected.succ_opt(),
+            KeyCode::Home => Some(self.today),
+            KeyCode::Char('m') => {
+                self.metric = match self.metric {
+                    Metric::FocusedMinutes => Metric::Pomodoros,
+                    Metric::Pomodoros => Metric::FocusedMinutes,
+                };
+                return ScreenKey::Handled;
+            }
+            KeyCode::Char('p') => {
+                self.cycle_filter(true);
+                return ScreenKey::Handled;
+            }
+            KeyCode::Char('P') => {
+                self.cycle_filter(false);
+                return ScreenKey::Handled;
+            }
+            _ => return ScreenKey::Ignored,
+        };
+
+        if let Some(selected) = selected {
+            self.selected = selected.min(self.today);
+        }
+        ScreenKey::Handled
+    }
+
+    fn history_changed(&mut self, history: &History, streaks: Streaks) {
+        let filter = self.active_filter().cloned();
+        let mut screen = Self::new(history, streaks);
+        screen.selected = self.selected;
+        screen.metric = self.metric;
+        screen.filter = filter.and_then(|filter| screen.filters.iter().position(|f| *f == filter));
+        screen.apply_filter();
+        *self = screen;
+    }
+}
+
+impl StatsScreen {
+    /// Build the screen from the session history, with today selected.
+    pub fn new(history: &History, streaks: Streaks) -> Self {
+        let today = Local::now().date_naive();
+        Self {
+            totals: daily_totals(history.records()),
+            sessions: history.records().to_vec(),
+            all_sessions: history.records().to_vec(),
+            filters: LabelFilter::all(history.records()),
+            filter: None,
+            today,
+            selected: today,
+            metric: Metric::FocusedMinutes,
+            streaks,
+        }
+    }
+
+    /// Switch to the next or previous project or tag, going through no filter
+    /// between the last and the first.
+    fn cycle_filter(&mut self, forward: bool) {
+        let count = self.filters.len();
+        self.filter = match (self.filter, forward) {
+            (_, _) if count == 0 => None,
+            (None, true) => Some(0),
+            (None, false) => Some(count - 1),
+            (Some(index), true) => Some(index + 1).filter(|next| *next < count),
+            (Some(index), false) => index.checked_sub(1),
+        };
+        self.apply_filter();
+    }
+
+    /// Keep the sessions matching the filter in use.
+    fn apply_filter(&mut self) {
+        let filter = self.active_filter();
+        self.sessions = self
+            .all_sessions
+            .iter()
+            .filter(|record| filter.map_or(true, |filter| filter.matches(record)))
+            .cloned()
+            .collect();
+        self.totals = daily_totals(&self.sessions);
+    }
+
+    fn active_filter(&self) -> Option<&LabelFilter> {
+        self.filter.and_then(|index| self.filters.get(index))
+    }
+
+    /// First day (a Monday) of the displayed window of `weeks` weeks.
+    ///
+    /// The window ends with the selected month, or today for the current month,
+    /// and always contains the selected day.
+    fn window_start(&self, weeks: u16) -> NaiveDate {
+        let month_end = self
+            .selected
+            .with_day(1)
+            .and_then(|first| first.checked_add_months(Months::new(1)))
+            .and_then(|next_month| next_month.pred_opt())
+            .unwrap_or(self.selected);
+        let end = month_end.min(self.today);
+
+        let span = Days::new(u64::from(weeks.saturating_sub(1)) * 7);
+        let start = week_start(end).checked_sub_days(span).unwrap_or(end);
+        if self.selected < start {
+            week_start(self.selected)
+        } else {
+            start
+        }
+    }
+
+    /// Render the heatmap with month labels, weekday labels and a legend.
+    fn render_heatmap(&self, frame: &mut Frame, area: Rect) {
+        let title = self.active_filter().map_or_else(
+            || "Focus Heatmap".to_string(),
+            |filter| format!("Focus Heatmap · {filter}"),
+        );
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(title);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let weeks = (inner.width.saturating_sub(LABEL_WIDTH) / CELL_WIDTH).clamp(1, MAX_WEEKS);
+        let start = self.window_start(weeks);
+        let days: Vec<NaiveDate> = start.iter_days().take(usize::from(weeks) * 7).collect();
+
+        let max = days
+            .iter()
+            .filter_map(|day| self.totals.get(day))
+            .map(|totals| self.metric.value(totals))
+            .max()
+            .unwrap_or(0);
+
+        let mut lines = Vec::with_capacity(9);
+
+        // Month labels above the first week of each month
+        let mut months = " ".repeat(usize::from(LABEL_WIDTH + weeks * CELL_WIDTH));
+        let mut previous_month = None;
+        let mut free_from = 0;
+        for (week, monday) in days.iter().step_by(7).enumerate() {
+            // The first, partial month is only labelled if it spans a few weeks
+            let partial = week == 0
+                && days
+                    .get(14)
+                    .is_some_and(|later| later.month() != monday.month());
+            if previous_month != Some(monday.month()) && !partial {
+                previous_month = Some(monday.month());
+                let column = usize::from(LABEL_WIDTH) + week * usize::from(CELL_WIDTH);
+                let label = monday.format("%b").to_string();
+                // Skip labels that would run into the previous one or off the edge
+                if column >= free_from && column + label.len() <= months.len() {
+                    months.replace_range(column..column + label.len(), &label);
+                    free_from = column + label.len() + 1;
+                }
+            }
+        }
+        lines.push(Line::from(Span::styled(
+            months,
+            Style::default().fg(Color::Gray),
+        )));
+
+        // One row per weekday, one column per week
+        for (weekday, label) in ["Mon", "", "Wed", "", "Fri", "", "Sun"].iter().enumerate() {
+            let mut spans = vec![Span::styled(
+                format!("{label:<width$}", width = usize::from(LABEL_WIDTH)),
+                Style::default().fg(Color::Gray),
+            )];
+            for day in days.iter().skip(weekday).step_by(7) {
+                spans.push(self.cell(*day, max));
+                spans.push(Span::raw(" "));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        // Legend
+        let mut legend = vec![Span::styled("Less ", Style::default().fg(Color::Gray))];
+        legend.extend(
+            LEVEL_COLORS
+                .iter()
+                .map(|color| Span::styled("■ ", Style::default().fg(*color))),
+        );
+        legend.push(Span::styled(
+            format!("More · {}", self.metric.label()),
+            Style::default().fg(Color::Gray),
+        ));
+        legend.push(Span::styled(
+            format!(
+                " · Current streak: {} · Longest: {}",
+                self.streaks.current, self.streaks.longest
+            ),
+            Style::default().fg(Color::Yellow),
+        ));
+        lines.push(Line::from(legend));
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    /// A single heatmap cell, coloured by the day's value relative to the busiest day.
+    fn cell(&self, day: NaiveDate, max: u64) -> Span<'static> {
+        if day > self.today {
+            return Span::raw(" ");
+        }
+
+        let value = self
+            .totals
+            .get(&day)
+            .map_or(0, |totals| self.metric.value(totals));
+        let level = if value == 0 || max == 0 {
+            0
+        } else {
+            usize::try_from((value * 4).div_ceil(max)).map_or(4, |level| level.clamp(1, 4))
+        };
+
+        let mut style = Style::default().fg(LEVEL_COLORS[level]);
+        if day == self.selected {
+            style = style.bg(Color::White);
+        }
+        Span::styled("■", style)
+    }
+
+    /// Render the totals and sessions of the selected day.
+    fn render_day(&self, frame: &mut Frame, area: Rect) {
+        let totals = self.totals.get(&self.selected).copied().unwrap_or_default();
+        let mut lines = vec![Line::from(Span::styled(
+            format!(
+                "{} pomodoros · {} focused minutes",
+                totals.pomodoros,
+                totals.focused_minutes()
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        ))];
+
+        let day_sessions: Vec<&SessionRecord> = self
+            .sessions
+            .iter()
+            .filter(|record| record.started_at.date_naive() == self.selected)
+            .collect();
+        if day_sessions.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No sessions recorded on this day",
+                Style::default().fg(Color::Gray),
+            )));
+        }
+        for record in day_sessions {
+            let color = if record.kind == SessionKind::Work {
+                Color::Red
+            } else {
+                Color::Green
+            };
+            let status = if record.completed { "✓" } else { "✗" };
+            let mut spans = vec![
+                Span::raw(format!(
+                    "{}–{}  ",
+                    record.started_at.format("%H:%M"),
+                    record.ended_at.format("%H:%M")
+                )),
+                Span::styled(
+                    format!("{:<12}", record.kind.as_str()),
+                    Style::default().fg(color),
+                ),
+                Span::raw(format!("{:>4}m {status}  ", record.actual_secs / 60)),
+                Span::styled(record.name.clone(), Style::default().fg(Color::Cyan)),
+            ];
+            spans.extend(crate::ui::label_chips(
+                record.project.as_deref(),
+                &record.tags,
+            ));
+            spans.push(Span::styled(
+                record
+                    .rating
+                    .map(|rating| format!(" {}", crate::ui::rating_stars(Some(rating))))
+                    .unwrap_or_default(),
+                Style::default().fg(Color::Yellow),
+            ));
+            lines.push(Line::from(spans));
+            if !record.note.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    format!("             {}", record.note),
+                    Style::default()
+                        .fg(Color::Gray)
+                        .add_modifier(Modifier::ITALIC),
+                )));
+            }
+        }
+
+        let day = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(self.selected.format("%A %-d %B %Y").to_string()),
+        );
+        frame.render_widget(day, area);
+    }
+
+    /// Render the footer with navigation hints.
+    fn render_footer(frame: &mut Frame, area: Rect) {
+        let footer = Paragraph::new(
+            "[Arrows] Move | [PgUp/PgDn] Month | [Home] Today | [M] Metric | [P] Project/tag | [T] Back | [Q] Quit",
+        )
+        .style(Style::default().fg(Color::Gray))
+        .alignment(ratatui::layout::Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded),
+        );
+        frame.render_widget(footer, area);
+    }
+}
@@ -0,0 +1,148 @@
+//! Single-line text editor used by the input modals.
+//!
+//! Editing works on grapheme clusters, so accented letters and emoji are moved
+//! over and deleted as a whole, and the usual readline shortcuts are supported.
+//! Text wider than the field scrolls horizontally to keep the cursor visible.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{prelude::*, widgets::Paragraph};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// The text being edited and the cursor position within it.
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    value: String,
+    /// Byte offset of the cursor, always on a grapheme boundary.
+    cursor: usize,
+}
+
+impl TextInput {
+    /// An input holding `value`, with the cursor at the end.
+    pub fn with_value(value: &str) -> Self {
+        Self {
+            value: value.to_string(),
+            cursor: value.len(),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Insert text at the cursor, such as a paste. Line breaks become spaces.
+    pub fn insert_str(&mut self, text: &str) {
+        let text: String = text
+            .chars()
+            .map(|c| if c == '\n' || c == '\t' { ' ' } else { c })
+            .filter(|c| *c != '\r' && !c.is_control())
+            .collect();
+        self.value.insert_str(self.cursor, &text);
+        self.cursor += text.len();
+    }
+
+    /// Apply an editing key, returning whether it was used.
+    ///
+    /// Keys such as Enter, Esc and Tab are left to the caller.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        match key.code {
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.value.len(),
+            KeyCode::Char('b') if ctrl => self.cursor = self.previous_boundary(),
+            KeyCode::Char('f') if ctrl => self.cursor = self.next_boundary(),
+            KeyCode::Char('b') if alt => self.cursor = self.previous_word(),
+            KeyCode::Char('f') if alt => self.cursor = self.next_word(),
+            KeyCode::Char('u') if ctrl => self.delete_range(0, self.cursor),
+            KeyCode::Char('k') if ctrl => self.delete_range(self.cursor, self.value.len()),
+            KeyCode::Char('w') if ctrl => self.delete_range(self.previous_word(), self.cursor),
+            KeyCode::Char('d') if alt => self.delete_range(self.cursor, self.next_word()),
+            KeyCode::Char('h') if ctrl => self.delete_range(self.previous_boundary(), self.cursor),
+            KeyCode::Char('d') if ctrl => self.delete_range(self.cursor, self.next_boundary()),
+            KeyCode::Char(_) if ctrl || alt => return false,
+            KeyCode::Char(c) => self.insert_str(c.encode_utf8(&mut [0; 4])),
+            KeyCode::Backspace if ctrl || alt => {
+                self.delete_range(self.previous_word(), self.cursor);
+            }
+            KeyCode::Backspace => self.delete_range(self.previous_boundary(), self.cursor),
+            KeyCode::Delete => self.delete_range(self.cursor, self.next_boundary()),
+            KeyCode::Left if ctrl || alt => self.cursor = self.previous_word(),
+            KeyCode::Right if ctrl || alt => self.cursor = self.next_word(),
+            KeyCode::Left => self.cursor = self.previous_boundary(),
+            KeyCode::Right => self.cursor = self.next_boundary(),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.value.len(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn delete_range(&mut self, start: usize, end: usize) {
+        self.value.replace_range(start..end, "");
+        self.cursor = start;
+    }
+
+    /// Start of the grapheme before the cursor.
+    fn previous_boundary(&self) -> usize {
+        self.value[..self.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    /// End of the grapheme after the cursor.
+    fn next_boundary(&self) -> usize {
+        self.value[self.cursor..]
+            .graphemes(true)
+            .next()
+            .map_or(self.cursor, |grapheme| self.cursor + grapheme.len())
+    }
+
+    /// Start of the word before the cursor, skipping whitespace first.
+    fn previous_word(&self) -> usize {
+        self.value[..self.cursor]
+            .split_word_bound_indices()
+            .rev()
+            .find(|(_, word)| !word.trim().is_empty())
+            .map_or(0, |(index, _)| index)
+    }
+
+    /// End of the word after the cursor, skipping whitespace first.
+    fn next_word(&self) -> usize {
+        self.value[self.cursor..]
+            .split_word_bound_indices()
+            .find(|(_, word)| !word.trim().is_empty())
+            .map_or(self.value.len(), |(index, word)| {
+                self.cursor + index + word.len()
+            })
+    }
+
+    /// Draw the text in a single line, placing the terminal cursor when `focused`.
+    pub fn render(&self, frame: &mut Frame, area: Rect, style: Style, focused: bool) {
+        if area.is_empty() {
+            return;
+        }
+
+        // Scroll just enough to keep the cursor in the last column
+        let width = usize::from(area.width);
+        let before_cursor = self.value[..self.cursor].width();
+        let mut skipped = 0;
+        let mut start = 0;
+        for (index, grapheme) in self.value.grapheme_indices(true) {
+            if before_cursor.saturating_sub(skipped) < width {
+                break;
+            }
+            skipped += grapheme.width();
+            start = index + grapheme.len();
+        }
+
+        let visible = &self.value[start..];
+        frame.render_widget(Paragraph::new(visible).style(style), area);
+        if focused {
+            let column =
+                u16::try_from(before_cursor.saturating_sub(skipped)).unwrap_or(area.width - 1);
+            frame.set_cursor_position((area.x + column.min(area.width - 1), area.y));
+        }
+    }
+}